@@ -0,0 +1,95 @@
+use ::core::ffi::{
+	CStr, c_int, c_void,
+};
+use ::rse_cpp::{
+	RefConst, RefMut, VtObjectMut, vtable,
+};
+use ::rse_shared::cppdef::{
+	entities::{
+		CollideableVt, HandleEntityVt,
+	},
+	Contents,
+	Ray, Trace,
+};
+use ::rse_math::{
+	Vector, QAngle,
+};
+use ::rse_utl::cppdef::UtlVector;
+
+pub const INTERFACEVERSION_ENGINETRACE_SERVER: &CStr = c"EngineTraceServer003";
+
+/// Kind of objects that a trace is tested against.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub enum TraceType {
+	/// Test against both the world and entities.
+	#[default]
+	Everything = 0,
+	/// Test against the world only, without calling [`TraceFilterVt::should_hit_entity`].
+	WorldOnly,
+	/// Test against entities only, without testing against the world.
+	EntitiesOnly,
+	/// Test against everything, but without testing against static props.
+	EverythingFilterProps,
+}
+
+vtable! {
+	pub TraceFilterVt {
+		pub fn should_hit_entity(entity: VtObjectMut<HandleEntityVt>, contents_mask: Contents) -> bool;
+		pub fn get_trace_type() -> TraceType;
+	}
+}
+
+vtable! {
+	pub EntityEnumeratorVt {
+		/// Returns `false` to stop enumerating.
+		pub fn enum_entity(entity: VtObjectMut<HandleEntityVt>) -> bool;
+	}
+}
+
+// TODO: `CTraceListData`.
+pub type TraceListData = c_void;
+
+// TODO: `CPhysCollide`.
+pub type PhysCollide = c_void;
+
+// TODO: `Vector4D`.
+pub type Vector4D = [::core::ffi::c_float; 4];
+
+vtable! {
+	pub EngineTraceVt {
+		pub fn get_point_contents(abs_position: RefConst<Vector>, out_entity: *mut Option<VtObjectMut<HandleEntityVt>>) -> Contents;
+		pub fn get_point_contents_collideable(collide: VtObjectMut<CollideableVt>, abs_position: RefConst<Vector>) -> Contents;
+		pub fn clip_ray_to_entity(ray: RefConst<Ray>, mask: Contents, entity: VtObjectMut<HandleEntityVt>, out_trace: RefMut<Trace>);
+		pub fn clip_ray_to_collideable(ray: RefConst<Ray>, mask: Contents, collide: VtObjectMut<CollideableVt>, out_trace: RefMut<Trace>);
+		pub fn trace_ray(ray: RefConst<Ray>, mask: Contents, filter: VtObjectMut<TraceFilterVt>, out_trace: RefMut<Trace>);
+		pub fn setup_leaf_and_entity_list_ray(ray: RefConst<Ray>, trace_data: RefMut<TraceListData>);
+		pub fn setup_leaf_and_entity_list_box(box_min: RefConst<Vector>, box_max: RefConst<Vector>, trace_data: RefMut<TraceListData>);
+		pub fn trace_ray_against_leaf_and_entity_list(
+			ray: RefConst<Ray>, trace_data: RefMut<TraceListData>,
+			mask: Contents, filter: VtObjectMut<TraceFilterVt>, out_trace: RefMut<Trace>,
+		);
+		pub fn sweep_collideable(
+			collide: VtObjectMut<CollideableVt>,
+			abs_start: RefConst<Vector>, abs_end: RefConst<Vector>, angles: RefConst<QAngle>,
+			mask: Contents, filter: VtObjectMut<TraceFilterVt>, out_trace: RefMut<Trace>,
+		);
+		// MSVC places overloads in the v-table in reverse order.
+		#[cfg(not(windows))]
+		pub fn enumerate_entities_ray(ray: RefConst<Ray>, triggers: bool, enumerator: VtObjectMut<EntityEnumeratorVt>);
+		pub fn enumerate_entities_box(
+			abs_mins: RefConst<Vector>, abs_maxs: RefConst<Vector>, enumerator: VtObjectMut<EntityEnumeratorVt>,
+		);
+		#[cfg(windows)]
+		pub fn enumerate_entities_ray(ray: RefConst<Ray>, triggers: bool, enumerator: VtObjectMut<EntityEnumeratorVt>);
+		pub fn get_collideable(entity: VtObjectMut<HandleEntityVt>) -> Option<VtObjectMut<CollideableVt>>;
+		pub fn get_stat_by_index(index: c_int, clear: bool) -> c_int;
+		pub fn get_brushes_in_aabb(
+			mins: RefConst<Vector>, maxs: RefConst<Vector>, output: *mut UtlVector<c_int>, contents_mask: Contents,
+		);
+		pub fn get_collidable_from_displacements_in_aabb(mins: RefConst<Vector>, maxs: RefConst<Vector>) -> *mut PhysCollide;
+		pub fn get_brush_info(brush: c_int, planes_out: *mut UtlVector<Vector4D>, contents_out: *mut c_int) -> bool;
+		pub fn point_outside_world(test: RefConst<Vector>) -> bool;
+		pub fn get_leaf_containing_point(test: RefConst<Vector>) -> c_int;
+	}
+}
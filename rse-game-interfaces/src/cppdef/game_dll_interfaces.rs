@@ -9,6 +9,7 @@ use ::rse_shared::cppdef::{
 	entities::{
 		edict_t, ServerClass, PvsInfo,
 		CollideableVt,
+		MAX_EDICTS,
 	},
 	client_textmessage_t, con_nprint_t, player_info_t,
 	NetChannelInfoVt,
@@ -23,12 +24,93 @@ use ::rse_math::{
 };
 use ::rse_scratch_pad::cppdef::ScratchPad3DVt;
 
-// TODO: `CBitVec`.
-pub type BitVec = c_void;
+/// Maximum number of players that can ever be on a server.
+pub const ABSOLUTE_PLAYER_LIMIT: usize = 255;
+
+/// Returns the number of integers needed by a [`BitVec`] of `n_bits` bits.
+pub const fn bit_vec_ints(n_bits: usize) -> usize {
+	n_bits.div_ceil(u32::BITS as usize)
+}
+
+/// Fixed-size vector of bits (`CBitVec`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct BitVec<const N_INTS: usize> {
+	pub ints: [u32; N_INTS],
+}
+
+impl<const N_INTS: usize> Default for BitVec<N_INTS> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<const N_INTS: usize> BitVec<N_INTS> {
+	/// Number of bits in the vector.
+	pub const BITS: usize = N_INTS * u32::BITS as usize;
+
+	/// Returns a new vector with all bits cleared.
+	pub const fn new() -> Self {
+		Self {
+			ints: [0; N_INTS],
+		}
+	}
+
+	/// Returns `true` if `bit` is set, or `false` if it is out of range.
+	pub const fn get(&self, bit: usize) -> bool {
+		bit < Self::BITS && (self.ints[bit / 32] & (1 << (bit % 32))) != 0
+	}
+
+	/// Sets `bit`.
+	/// 
+	/// # Panics
+	/// `bit` must be in range.
+	pub const fn set(&mut self, bit: usize) {
+		self.ints[bit / 32] |= 1 << (bit % 32);
+	}
+
+	/// Clears `bit`.
+	/// 
+	/// # Panics
+	/// `bit` must be in range.
+	pub const fn clear(&mut self, bit: usize) {
+		self.ints[bit / 32] &= !(1 << (bit % 32));
+	}
+
+	/// Clears all bits.
+	pub const fn clear_all(&mut self) {
+		self.ints = [0; N_INTS];
+	}
+
+	/// Returns `true` if no bits are set.
+	pub fn is_empty(&self) -> bool {
+		self.ints.iter().all(move |&i| i == 0)
+	}
+
+	/// Returns an iterator over the indices of all set bits, in ascending order.
+	pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+		(0..Self::BITS).filter(move |&bit| self.get(bit))
+	}
+}
+
+/// Bit vector with a bit for each player slot (`CPlayerBitVec`).
+/// 
+/// Bit `i` corresponds to the player with the entity index `i + 1`.
+pub type PlayerBitVec = BitVec<{bit_vec_ints(ABSOLUTE_PLAYER_LIMIT)}>;
+
+/// Bit vector with a bit for each edict.
+pub type EdictBitVec = BitVec<{bit_vec_ints(MAX_EDICTS)}>;
 
-// TODO: `IRecipientFilter`.
 vtable! {
-	pub RecipientFilterVt {}
+	pub RecipientFilterVt {
+		pub fn destructor();
+		#[cfg(not(windows))]
+		pub fn destructor_2();
+		pub fn is_reliable() -> bool;
+		pub fn is_init_message() -> bool;
+		pub fn get_recipient_count() -> c_int;
+		pub fn get_recipient_index(slot: c_int) -> c_int;
+	}
 }
 
 // TODO: `ISpatialPartition`.
@@ -98,7 +180,7 @@ vtable! {
 		pub fn is_model_precached(s: *const c_char) -> bool;
 		pub fn is_decal_precached(s: *const c_char) -> bool;
 		pub fn is_generic_precached(s: *const c_char) -> bool;
-		pub fn get_cluster_for_origin(org: RefConst<Vector>);
+		pub fn get_cluster_for_origin(org: RefConst<Vector>) -> c_int;
 		pub fn get_pvs_for_cluster(cluster: c_int, output_pvs_length: c_int, output_pvs: *mut c_uchar) -> c_int;
 		pub fn check_origin_in_pvs(org: RefConst<Vector>, check_pvs: *const c_uchar, check_pvs_size: c_int) -> bool;
 		pub fn check_box_in_pvs(
@@ -148,7 +230,7 @@ vtable! {
 			low_priority: bool,
 		);
 		pub fn message_determine_multicast_recipients(
-			use_pas: bool, origin: RefConst<Vector>, player_bits: RefMut<BitVec>,
+			use_pas: bool, origin: RefConst<Vector>, player_bits: RefMut<PlayerBitVec>,
 		);
 		pub fn entity_message_begin(ent_index: c_int, ent_class: *mut ServerClass, reliable: bool) -> *mut BfWrite;
 		pub fn user_message_begin(filter: VtObjectMut<RecipientFilterVt>, msg_type: c_int) -> *mut BfWrite;
@@ -196,7 +278,7 @@ vtable! {
 		) -> VtObjectMut<SpatialPartitionVt>;
 		pub fn destroy_spatial_partition(VtObjectMut<SpatialPartitionVt>);
 		pub fn draw_map_to_scratch_pad(pad: VtObjectMut<ScratchPad3DVt>, flags: c_int);
-		pub fn get_entity_transmit_bits_for_client(client_index: c_int) -> *const BitVec;
+		pub fn get_entity_transmit_bits_for_client(client_index: c_int) -> *const EdictBitVec;
 		pub fn is_paused() -> bool;
 		pub fn force_exact_file(s: *const c_char);
 		pub fn force_model_bounds(s: *const c_char, mins: RefConst<Vector>, maxs: RefConst<Vector>);
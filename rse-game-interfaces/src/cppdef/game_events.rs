@@ -39,9 +39,9 @@ vtable! {
 		pub fn create_event(name: *const c_char, force: bool) -> Option<VtObjectMut<GameEventVt>>;
 		pub fn fire_event(event: VtObjectMut<GameEventVt>, dont_broadcast: bool) -> bool;
 		pub fn fire_event_client_side(event: VtObjectMut<GameEventVt>) -> bool;
-		pub fn duplicate_event(event: VtObjectMut<GameEventVt>) -> VtObjectMut<GameEventVt>;
+		pub fn duplicate_event(event: VtObjectMut<GameEventVt>) -> Option<VtObjectMut<GameEventVt>>;
 		pub fn free_event(event: VtObjectMut<GameEventVt>);
-		pub fn serialize_event(event: VtObjectMut<GameEventVt>, buf: BfWrite) -> bool;
+		pub fn serialize_event(event: VtObjectMut<GameEventVt>, buf: *mut BfWrite) -> bool;
 		pub fn unserialize_event(buf: *mut BfRead) -> VtObjectPtr<GameEventVt>;
 	}
 }
@@ -1,5 +1,7 @@
 mod cvar;
 pub use cvar::*;
+mod engine_trace;
+pub use engine_trace::*;
 mod file_system;
 pub use file_system::*;
 mod game_dll_interfaces;
@@ -68,7 +68,7 @@ vtable! {
 
 pub type BotCmd = WithVTable<BotCmdVt, BotCmdExt>;
 
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
 #[repr(C)]
 pub struct BotCmdExt {
 	/// Number for matching server and client commands for debugging.
@@ -93,7 +93,7 @@ pub struct BotCmdExt {
 	/// negative values indicate movement downwards.
 	pub up_move: c_float,
 	/// Bit-set of button states.
-	pub buttons: c_int,
+	pub buttons: Buttons,
 	/// Impulse command issued,
 	/// or `0` if no command was issued.
 	pub impulse: u8,
@@ -118,6 +118,46 @@ pub struct BotCmdExt {
 	pub has_been_predicted: bool,
 }
 
+::rse_cpp::bitflags! {
+	/// Bit-set of the buttons held by a player (`IN_*`).
+	#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	#[repr(transparent)]
+	pub struct Buttons: c_int {
+		const ATTACK = 1 << 0;
+		const JUMP = 1 << 1;
+		const DUCK = 1 << 2;
+		const FORWARD = 1 << 3;
+		const BACK = 1 << 4;
+		const USE = 1 << 5;
+		const CANCEL = 1 << 6;
+		const LEFT = 1 << 7;
+		const RIGHT = 1 << 8;
+		const MOVE_LEFT = 1 << 9;
+		const MOVE_RIGHT = 1 << 10;
+		const ATTACK2 = 1 << 11;
+		const RUN = 1 << 12;
+		const RELOAD = 1 << 13;
+		const ALT1 = 1 << 14;
+		const ALT2 = 1 << 15;
+		/// Used by the client to show the scoreboard.
+		const SCORE = 1 << 16;
+		/// Player is holding the speed key.
+		const SPEED = 1 << 17;
+		/// Player is holding the walk key.
+		const WALK = 1 << 18;
+		/// Zoom key for the HUD zoom.
+		const ZOOM = 1 << 19;
+		/// Weapon-defined.
+		const WEAPON1 = 1 << 20;
+		/// Weapon-defined.
+		const WEAPON2 = 1 << 21;
+		const BULLRUSH = 1 << 22;
+		const GRENADE1 = 1 << 23;
+		const GRENADE2 = 1 << 24;
+		const ATTACK3 = 1 << 25;
+	}
+}
+
 vtable! {
 	pub BotCmdVt {
 		pub fn destructor();
@@ -129,7 +169,7 @@ vtable! {
 vtable! {
 	pub BotControllerVt {
 		pub fn set_abs_origin(vec: RefMut<Vector>);
-		pub fn set_abs_angles(ang: RefMut<Vector>);
+		pub fn set_abs_angles(ang: RefMut<QAngle>);
 		pub fn set_local_origin(origin: RefConst<Vector>);
 		pub fn get_local_origin() -> Vector;
 		pub fn set_local_angles(angles: RefConst<QAngle>);
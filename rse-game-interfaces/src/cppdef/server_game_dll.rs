@@ -1,11 +1,14 @@
 use ::core::ffi::{
-	CStr, c_char, c_float, c_int,
+	CStr, c_char, c_float, c_int, c_void,
+};
+use ::rse_cpp::{
+	RefMut, VtObjectMut, vtable,
 };
 use ::rse_shared::cppdef::{
 	entities::{
 		edict_t, ServerClass,
 	},
-	GlobalVars,
+	GlobalVars, SaveRestoreData,
 };
 use ::rse_interface::CreateInterfaceFn;
 
@@ -18,7 +21,78 @@ pub const INTERFACEVERSION_SERVERGAMEDLL_INT: c_int = 12;
 
 pub type TickInterval = c_float;
 
-::rse_cpp::vtable! {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub enum QueryCvarValueStatus {
+	/// Got the value fine.
+	ValueIntact = 0,
+	/// There is no ConVar or ConCommand with the given name.
+	CvarNotFound = 1,
+	/// There's a ConCommand, but it's not a ConVar.
+	NotACvar = 2,
+	/// The cvar was marked with `FCVAR_SERVER_CAN_NOT_QUERY`,
+	/// so the server is not allowed to have its value.
+	CvarProtected = 3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct QueryCvarCookie(pub c_int);
+impl QueryCvarCookie {
+	pub const INVALID: Self = Self(-1);
+
+	pub const fn is_valid(&self) -> bool {
+		self.0 != Self::INVALID.0
+	}
+
+	pub const fn is_invalid(&self) -> bool {
+		self.0 == Self::INVALID.0
+	}
+}
+
+// TODO: `datamap_t`.
+#[allow(non_camel_case_types)]
+pub type datamap_t = c_void;
+
+// TODO: `typedescription_t`.
+#[allow(non_camel_case_types)]
+pub type typedescription_t = c_void;
+
+// TODO: `CStandardSendProxies`.
+pub type StandardSendProxies = c_void;
+
+// TODO: `IServerGCLobby`.
+vtable! {
+	pub ServerGcLobbyVt {}
+}
+
+/// Function used by [`ServerGameDllVt::status`] to print output.
+pub type StatusPrintFn = unsafe extern "C" fn(fmt: *const c_char, ...);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub enum PrepareLevelResourcesResult {
+	/// The resources are ready.
+	Prepared,
+	/// The game DLL is loading the resources asynchronously,
+	/// and should be called again later.
+	InProgress,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub enum CanProvideLevelResult {
+	/// The game DLL doesn't know about the level, so the engine has to provide it.
+	CannotProvide,
+	/// The game DLL can provide resources for the level,
+	/// and the map name was updated to its canonical name.
+	CanProvide,
+	/// The game DLL might be able to provide resources for the level,
+	/// but it can't be known without calling `prepare_level_resources`.
+	Possibly,
+}
+
+vtable! {
 	pub ServerGameDllVt {
 		pub fn dll_init(
 			engine_factory: CreateInterfaceFn, physics_factory: CreateInterfaceFn, file_system_factory: CreateInterfaceFn,
@@ -41,8 +115,62 @@ pub type TickInterval = c_float;
 		pub fn get_all_server_classes() -> *mut ServerClass;
 		pub fn get_game_description() -> *const c_char;
 		pub fn create_network_string_tables();
-		
-		// TODO: `SaveInit` and everything else after it.
+		pub fn save_init(size: c_int) -> *mut SaveRestoreData;
+		pub fn save_write_fields(
+			save_data: *mut SaveRestoreData, name: *const c_char, base_data: *mut c_void,
+			map: *mut datamap_t, fields: *mut typedescription_t, field_count: c_int,
+		);
+		pub fn save_read_fields(
+			save_data: *mut SaveRestoreData, name: *const c_char, base_data: *mut c_void,
+			map: *mut datamap_t, fields: *mut typedescription_t, field_count: c_int,
+		);
+		pub fn save_global_state(save_data: *mut SaveRestoreData);
+		pub fn restore_global_state(save_data: *mut SaveRestoreData);
+		pub fn pre_save(save_data: *mut SaveRestoreData);
+		pub fn save(save_data: *mut SaveRestoreData);
+		pub fn get_save_comment(
+			comment: *mut c_char, max_length: c_int,
+			minutes: c_float, seconds: c_float, no_time: bool,
+		);
+		pub fn write_save_headers(save_data: *mut SaveRestoreData);
+		pub fn read_restore_headers(save_data: *mut SaveRestoreData);
+		pub fn restore(save_data: *mut SaveRestoreData, create_players: bool);
+		pub fn is_restoring() -> bool;
+		pub fn create_entity_transition_list(save_data: *mut SaveRestoreData, level_mask: c_int) -> c_int;
+		pub fn build_adjacent_map_list();
+		pub fn get_user_message_info(msg_type: c_int, name: *mut c_char, max_name_length: c_int, size: RefMut<c_int>) -> bool;
+		pub fn get_standard_send_proxies() -> *mut StandardSendProxies;
+		pub fn post_init();
+		pub fn think(final_tick: bool);
+		#[cfg(feature = "xbox360")]
+		pub fn get_title_name(map_name: *const c_char, title_buff: *mut c_char, title_buff_size: c_int);
+		pub fn pre_save_game_loaded(save_name: *const c_char, currently_in_game: bool);
+		pub fn should_hide_server() -> bool;
+		pub fn invalidate_mdl_cache();
+		pub fn on_query_cvar_value_finished(
+			cookie: QueryCvarCookie,
+			player_entity: *mut edict_t,
+			status: QueryCvarValueStatus,
+			cvar_name: *const c_char, cvar_value: *const c_char,
+		);
+		pub fn game_server_steam_api_activated();
+		pub fn game_server_steam_api_shutdown();
+		pub fn set_server_hibernation(hibernating: bool);
+		pub fn get_server_gc_lobby() -> Option<VtObjectMut<ServerGcLobbyVt>>;
+		pub fn get_server_browser_map_override() -> *const c_char;
+		pub fn get_server_browser_game_data() -> *const c_char;
+		pub fn status(print: StatusPrintFn);
+		pub fn prepare_level_resources(
+			in_out_map_name: *mut c_char, map_name_size: usize,
+			in_out_map_file: *mut c_char, map_file_size: usize,
+		);
+		pub fn async_prepare_level_resources(
+			in_out_map_name: *mut c_char, map_name_size: usize,
+			in_out_map_file: *mut c_char, map_file_size: usize,
+			out_progress: *mut c_float,
+		) -> PrepareLevelResourcesResult;
+		pub fn can_provide_level(in_out_map_name: *mut c_char, map_name_max: c_int) -> CanProvideLevelResult;
+		pub fn is_manual_map_change_okay(out_reason: *mut *const c_char) -> bool;
 
 		// TODO: Figure out what the difference is between different `ServerGameDLL` versions.
 	}
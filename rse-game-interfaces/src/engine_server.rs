@@ -3,22 +3,34 @@ use ::core::{
 		CStr, c_int, c_float,
 	},
 	num::NonZero,
-	ptr::NonNull,
+	ptr::{
+		NonNull, null,
+	},
 };
 use ::rse_cpp::{
 	AsObject, virtual_call, owned_vt_object_wrapper,
 };
-use ::rse_shared::cppdef::{
-	SteamId,
-	SoundLevel,
+use ::rse_shared::{
+	bitbuf::BitWriter,
+	cppdef::{
+		entities::{
+			BaseHandle, MAX_EDICTS,
+		},
+		SteamId,
+		SoundLevel,
+	},
+	ServerEdict,
 };
 use ::rse_math::Vector;
 
 use crate::{
 	cppdef::{
 		VEngineServerVt, INTERFACEVERSION_VENGINESERVER,
+		PlayerBitVec,
 	},
 	InterfaceOfFactory, AppSystemFactory,
+	RecipientFilter, UserMessage, UserId,
+	Pvs, AreaBits,
 };
 
 /// Safe interface to `IVEngineServer`.
@@ -31,6 +43,23 @@ pub trait VEngineServerImpl: AsObject<VEngineServerVt> {
 	fn is_map_valid(&self, map_name: &CStr) -> bool {
 		(unsafe { virtual_call!(self.as_object() => is_map_valid(map_name.as_ptr())) }) != 0
 	}
+	/// Changes the level to `map_name`.
+	///
+	/// If `landmark` is `Some`, then the level change is a transition,
+	/// and the players are placed relative to the landmark entity of the same name.
+	fn change_level(&self, map_name: &CStr, landmark: Option<&CStr>) {
+		let landmark = landmark.map(CStr::as_ptr).unwrap_or(null());
+		unsafe { virtual_call!(self.as_object() => change_level(map_name.as_ptr(), landmark)) }
+	}
+	/// Returns the text of the entity lump of the current map.
+	///
+	/// See [`EntityLump`](rse_shared::entities::EntityLump) for parsing it.
+	fn map_entities_string(&self) -> &CStr {
+		unsafe {
+			let ptr = virtual_call!(self.as_object() => get_map_entities_string());
+			if ptr.is_null() { c"" } else { CStr::from_ptr(ptr) }
+		}
+	}
 	/// Returns `true` if the running server is a dedicated server.
 	/// 
 	/// # Thread safety
@@ -119,15 +148,169 @@ pub trait VEngineServerImpl: AsObject<VEngineServerVt> {
 		unsafe { virtual_call!(self.as_object() => set_paused_forced(paused, duration)) }
 	}
 
-	/// Returns the number of entities.
+	/// Returns the number of edicts that are in use.
 	fn entity_count(&self) -> usize {
 		(unsafe { virtual_call!(self.as_object() => get_entity_count()) }) as _
 	}
+	/// Returns the edict with the entity index `index`,
+	/// or `None` if it is out of range or free.
+	fn edict(&self, index: c_int) -> Option<&ServerEdict> {
+		unsafe {
+			let ptr = virtual_call!(self.as_object() => entity_of_ent_index(index));
+			ptr.as_ref().map(move |edict| ServerEdict::from_c_edict(edict))
+		}
+	}
+	/// Returns the entity index of `edict`.
+	fn index_of_edict(&self, edict: &ServerEdict) -> c_int {
+		unsafe { virtual_call!(self.as_object() => index_of_edict(edict.as_ptr())) }
+	}
+	/// Returns the edict of the entity that `handle` refers to,
+	/// or `None` if the handle is invalid or stale.
+	fn edict_of_handle(&self, handle: BaseHandle) -> Option<&ServerEdict> {
+		let edict = self.edict(handle.entry_index() as _)?;
+		edict.is_handle_of(handle).then_some(edict)
+	}
+	/// Returns an iterator over all edicts that are in use.
+	fn edicts(&self) -> Edicts<'_, Self> {
+		Edicts {
+			engine: self,
+			next: 0,
+			remaining: self.entity_count(),
+		}
+	}
+	/// Returns the user ID of the player of `edict`, or `None` if it isn't a player.
+	fn player_user_id(&self, edict: &ServerEdict) -> Option<UserId> {
+		let id = unsafe { virtual_call!(self.as_object() => get_player_user_id(edict.as_ptr())) };
+		(id != -1).then_some(id)
+	}
+
+	/// Creates a fake client (bot) named `name`,
+	/// returning `None` if there are no free player slots.
+	/// 
+	/// The game DLL doesn't control the new client; see [`BotManagerImpl`](crate::BotManagerImpl) for that.
+	fn create_fake_client(&self, name: &CStr) -> Option<&ServerEdict> {
+		unsafe {
+			let ptr = virtual_call!(self.as_object() => create_fake_client(name.as_ptr()));
+			ptr.as_ref().map(move |edict| ServerEdict::from_c_edict(edict))
+		}
+	}
+	/// Like [`create_fake_client`](Self::create_fake_client),
+	/// but `report_fake_client` controls whether the client is reported as a bot to the master server.
+	fn create_fake_client_ex(&self, name: &CStr, report_fake_client: bool) -> Option<&ServerEdict> {
+		unsafe {
+			let ptr = virtual_call!(self.as_object() => create_fake_client_ex(name.as_ptr(), report_fake_client));
+			ptr.as_ref().map(move |edict| ServerEdict::from_c_edict(edict))
+		}
+	}
+	/// Sets the value of the ConVar named `name` of the fake client of `edict`.
+	fn set_fake_client_con_var_value(&self, edict: &ServerEdict, name: &CStr, value: &CStr) {
+		unsafe { virtual_call!(self.as_object() => set_fake_client_convar_value(
+			edict.as_ptr() as _, name.as_ptr(), value.as_ptr(),
+		)) }
+	}
+
+	/// Returns the visibility cluster that contains `origin`, or `-1` if it's outside of the world.
+	fn cluster_for_origin(&self, origin: &Vector) -> c_int {
+		unsafe { virtual_call!(self.as_object() => get_cluster_for_origin(NonNull::from(origin))) }
+	}
+	/// Returns the PVS of the visibility cluster `cluster`.
+	fn pvs_for_cluster(&self, cluster: c_int) -> Pvs {
+		let mut pvs = Pvs::new();
+		let buffer = pvs.buffer_mut();
+		let len = unsafe {
+			virtual_call!(self.as_object() => get_pvs_for_cluster(cluster, buffer.len() as _, buffer.as_mut_ptr()))
+		};
+		pvs.set_len(len.max(0) as _);
+		pvs
+	}
+	/// Returns the PVS of the visibility cluster that contains `origin`.
+	fn pvs_for_origin(&self, origin: &Vector) -> Pvs {
+		self.pvs_for_cluster(self.cluster_for_origin(origin))
+	}
+	/// Returns `true` if `origin` is in a cluster of `pvs`.
+	fn is_origin_in_pvs(&self, origin: &Vector, pvs: &Pvs) -> bool {
+		let bytes = pvs.as_bytes();
+		unsafe { virtual_call!(self.as_object() => check_origin_in_pvs(
+			NonNull::from(origin), bytes.as_ptr(), bytes.len() as _,
+		)) }
+	}
+	/// Returns `true` if the box from `mins` to `maxs` touches a cluster of `pvs`.
+	fn is_box_in_pvs(&self, mins: &Vector, maxs: &Vector, pvs: &Pvs) -> bool {
+		let bytes = pvs.as_bytes();
+		unsafe { virtual_call!(self.as_object() => check_box_in_pvs(
+			NonNull::from(mins), NonNull::from(maxs), bytes.as_ptr(), bytes.len() as _,
+		)) }
+	}
+
+	/// Returns the area that contains `origin`.
+	fn area(&self, origin: &Vector) -> c_int {
+		unsafe { virtual_call!(self.as_object() => get_area(NonNull::from(origin))) }
+	}
+	/// Returns `true` if `area1` and `area2` are connected through open area portals.
+	fn areas_connected(&self, area1: c_int, area2: c_int) -> bool {
+		(unsafe { virtual_call!(self.as_object() => check_areas_connected(area1, area2)) }) != 0
+	}
+	/// Returns the areas that can be seen from `area`.
+	fn area_bits(&self, area: c_int) -> AreaBits {
+		let mut bits = AreaBits::default();
+		unsafe { virtual_call!(self.as_object() => get_area_bits(area, bits.0.as_mut_ptr(), bits.0.len() as _)) }
+		bits
+	}
+	/// Opens or closes the area portal numbered `portal`.
+	fn set_area_portal_state(&mut self, portal: c_int, is_open: bool) {
+		unsafe { virtual_call!(self.as_object() => set_area_portal_state(portal, is_open as _)) }
+	}
+	/// Opens or closes multiple area portals at once, given their numbers and whether they should be open.
+	fn set_area_portal_states(&mut self, states: impl IntoIterator<Item = (c_int, bool)>) {
+		const CHUNK: usize = 64;
+		let mut states = states.into_iter().peekable();
+		while states.peek().is_some() {
+			let mut portals = [0; CHUNK];
+			let mut open = [0; CHUNK];
+			let mut n = 0;
+			for (portal, is_open) in states.by_ref().take(CHUNK) {
+				portals[n] = portal;
+				open[n] = is_open as c_int;
+				n += 1;
+			}
+			unsafe { virtual_call!(self.as_object() => set_area_portal_states(portals.as_ptr(), open.as_ptr(), n as _)) }
+		}
+	}
+
+	/// Returns the players whose PVS (or PAS, if `use_pas` is `true`) contains `origin`.
+	fn multicast_recipients(&self, use_pas: bool, origin: &Vector) -> PlayerBitVec {
+		let mut bits = PlayerBitVec::new();
+		unsafe {
+			virtual_call!(self.as_object() => message_determine_multicast_recipients(
+				use_pas, NonNull::from(origin), NonNull::from(&mut bits),
+			))
+		}
+		bits
+	}
+	/// Begins a user message of type `msg_type` for the recipients of `filter`,
+	/// returning `None` if the engine didn't start the message.
+	/// 
+	/// The fields of the message are written with the returned [`UserMessage`],
+	/// and the message is sent when it is dropped.
+	fn user_message<'a>(&'a mut self, filter: &'a mut RecipientFilter, msg_type: c_int) -> Option<UserMessage<'a>> {
+		unsafe {
+			let buffer = virtual_call!(self.as_object() => user_message_begin(filter.as_mut_ptr(), msg_type));
+			if buffer.is_null() {
+				return None
+			}
+			Some(UserMessage::new(self.as_object(), BitWriter::from_mut_ptr(buffer)))
+		}
+	}
+
 	/// Returns the value of the named ConVar of a client.
 	fn client_con_var_value<'a>(&'a self, client_index: c_int, name: &CStr) -> &'a CStr {
 		let ptr = unsafe { virtual_call!(self.as_object() => get_client_convar_value(client_index, name.as_ptr())) };
 		unsafe { CStr::from_ptr(ptr) }
 	}
+	/// Prints `message` to the console of the client of `edict`.
+	fn client_printf(&self, edict: &ServerEdict, message: &CStr) {
+		unsafe { virtual_call!(self.as_object() => client_printf(edict.as_ptr() as _, message.as_ptr())) }
+	}
 	/// Emits an ambient sound.
 	fn emit_ambient_sound(&mut self, options: EmitSound<'_>) {
 		unsafe { virtual_call!(
@@ -192,6 +375,35 @@ pub type Model = NonZero<c_int>;
 pub type Decal = c_int;
 pub type Generic = c_int;
 
+/// Iterator over the edicts that are in use, in order of their entity indices.
+///
+/// See [`VEngineServerImpl::edicts`].
+pub struct Edicts<'a, E: ?Sized> {
+	engine: &'a E,
+	next: c_int,
+	/// Number of edicts in use that haven't been returned yet.
+	remaining: usize,
+}
+
+impl<'a, E: ?Sized + VEngineServerImpl> Iterator for Edicts<'a, E> {
+	type Item = &'a ServerEdict;
+	fn next(&mut self) -> Option<Self::Item> {
+		while self.remaining > 0 && (self.next as usize) < MAX_EDICTS {
+			let index = self.next;
+			self.next += 1;
+			if let Some(edict) = self.engine.edict(index) {
+				self.remaining -= 1;
+				return Some(edict)
+			}
+		}
+		None
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, Some(self.remaining))
+	}
+}
+
 owned_vt_object_wrapper! {
 	pub struct VEngineServer for VEngineServerVt;
 }
@@ -0,0 +1,162 @@
+use ::core::{
+	ffi::CStr,
+	ptr::{
+		NonNull, null_mut,
+	},
+};
+use ::rse_cpp::{
+	AsObject, VtObjectPtr, VtObjectMut, virtual_call, owned_vt_object_wrapper,
+	new_vtable_self, this_to_self,
+};
+use ::rse_math::Vector;
+use ::rse_shared::cppdef::{
+	entities::HandleEntityVt,
+	Contents, Ray, Trace,
+};
+
+use crate::{
+	cppdef::{
+		EngineTraceVt, INTERFACEVERSION_ENGINETRACE_SERVER,
+		TraceFilterVt, TraceType,
+	},
+	InterfaceOfFactory, AppSystemFactory,
+};
+
+/// Filter that decides which entities a trace can hit (`ITraceFilter`).
+///
+/// Closures that take the entity and the contents mask of the trace implement this trait.
+pub trait TraceFilter {
+	/// Returns `true` if the trace should be tested against `entity`.
+	fn should_hit_entity(&mut self, entity: VtObjectMut<HandleEntityVt>, contents_mask: Contents) -> bool;
+	/// Returns the kind of objects that the trace is tested against.
+	fn trace_type(&self) -> TraceType {
+		TraceType::Everything
+	}
+}
+impl<F: FnMut(VtObjectMut<HandleEntityVt>, Contents) -> bool> TraceFilter for F {
+	fn should_hit_entity(&mut self, entity: VtObjectMut<HandleEntityVt>, contents_mask: Contents) -> bool {
+		self(entity, contents_mask)
+	}
+}
+
+/// [`TraceFilter`] that only hits the world.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WorldOnly;
+impl TraceFilter for WorldOnly {
+	fn should_hit_entity(&mut self, _: VtObjectMut<HandleEntityVt>, _: Contents) -> bool {
+		false
+	}
+	fn trace_type(&self) -> TraceType {
+		TraceType::WorldOnly
+	}
+}
+
+/// C++ object of a [`TraceFilter`] implemented in Rust.
+#[repr(C)]
+pub struct TraceFilterObject<T> {
+	vtable: NonNull<TraceFilterVt>,
+	inner: T,
+}
+
+impl<T> TraceFilterObject<T>
+where
+	T: TraceFilter,
+{
+	pub const fn new(inner: T) -> Self {
+		Self {
+			vtable: unsafe { NonNull::new_unchecked(Self::VTABLE as *const _ as *mut _) },
+			inner,
+		}
+	}
+
+	pub const fn as_inner(&self) -> &T {
+		&self.inner
+	}
+
+	pub const fn as_inner_mut(&mut self) -> &mut T {
+		&mut self.inner
+	}
+
+	pub fn into_inner(self) -> T {
+		self.inner
+	}
+
+	/// Returns a pointer to the C++ object, which is valid for as long as `self` is borrowed.
+	pub fn as_ptr(&mut self) -> VtObjectMut<TraceFilterVt> {
+		NonNull::from(self).cast()
+	}
+
+	const VTABLE: &TraceFilterVt = &new_vtable_self!(TraceFilterVt {
+		should_hit_entity,
+		get_trace_type
+	});
+
+	::rse_cpp::vtable_methods! {
+		this: VtObjectPtr<TraceFilterVt>;
+		fn should_hit_entity(entity: VtObjectMut<HandleEntityVt>, contents_mask: Contents) -> bool {
+			this_to_self!(mut this).inner.should_hit_entity(entity, contents_mask)
+		}
+		fn get_trace_type() -> TraceType {
+			this_to_self!(ref this).inner.trace_type()
+		}
+	}
+}
+
+/// Safe interface to `IEngineTrace`.
+pub trait EngineTraceImpl: AsObject<EngineTraceVt> {
+	/// Returns the contents of the world and entities at `position`.
+	fn point_contents(&self, position: &Vector) -> Contents {
+		unsafe { virtual_call!(self.as_object() => get_point_contents(NonNull::from(position), null_mut())) }
+	}
+	/// Traces `ray` through the world and the entities accepted by `filter`,
+	/// stopping at anything with contents in `mask`.
+	fn trace_ray(&self, ray: &Ray, mask: Contents, filter: impl TraceFilter) -> Trace {
+		let mut filter = TraceFilterObject::new(filter);
+		// SAFETY: Every field of the trace is valid when zeroed, and the engine clears it anyway.
+		let mut trace: Trace = unsafe { ::core::mem::zeroed() };
+		unsafe { virtual_call!(self.as_object() => trace_ray(
+			NonNull::from(ray), mask, filter.as_ptr(), NonNull::from(&mut trace),
+		)) }
+		trace
+	}
+	/// Traces a line from `start` to `end`.
+	///
+	/// See [`trace_ray`](Self::trace_ray).
+	fn trace_line(&self, start: Vector, end: Vector, mask: Contents, filter: impl TraceFilter) -> Trace {
+		self.trace_ray(&Ray::line(start, end), mask, filter)
+	}
+	/// Sweeps the box from `mins` to `maxs` from `start` to `end`.
+	///
+	/// See [`trace_ray`](Self::trace_ray).
+	fn trace_hull(
+		&self,
+		start: Vector, end: Vector, mins: Vector, maxs: Vector,
+		mask: Contents, filter: impl TraceFilter,
+	) -> Trace {
+		self.trace_ray(&Ray::hull(start, end, mins, maxs), mask, filter)
+	}
+	/// Traces `ray` against `entity` only.
+	fn clip_ray_to_entity(&self, ray: &Ray, mask: Contents, entity: VtObjectMut<HandleEntityVt>) -> Trace {
+		// SAFETY: See `trace_ray`.
+		let mut trace: Trace = unsafe { ::core::mem::zeroed() };
+		unsafe { virtual_call!(self.as_object() => clip_ray_to_entity(
+			NonNull::from(ray), mask, entity, NonNull::from(&mut trace),
+		)) }
+		trace
+	}
+	/// Returns `true` if `point` is outside of the world.
+	fn is_point_outside_world(&self, point: &Vector) -> bool {
+		unsafe { virtual_call!(self.as_object() => point_outside_world(NonNull::from(point))) }
+	}
+}
+impl<T: ?Sized + AsObject<EngineTraceVt>> EngineTraceImpl for T {}
+
+owned_vt_object_wrapper! {
+	pub struct EngineTrace for EngineTraceVt;
+}
+unsafe impl ::rse_interface::Interface for EngineTrace {
+	const IDENTIFIER: &CStr = INTERFACEVERSION_ENGINETRACE_SERVER;
+}
+impl InterfaceOfFactory for EngineTrace {
+	type Factory = AppSystemFactory;
+}
@@ -61,6 +61,10 @@ where
 		&mut self.inner
 	}
 
+	pub fn into_inner(self) -> T {
+		self.inner
+	}
+
 	const VTABLE: &GameEventListener2Vt = &new_vtable_self!(GameEventListener2Vt {
 		destructor,
 		#[cfg(not(windows))]
@@ -1,16 +1,97 @@
-use ::core::ffi::CStr;
+use ::alloc::boxed::Box;
+use ::core::{
+	ffi::{
+		CStr, c_int,
+	},
+	mem::ManuallyDrop,
+	ops::{
+		Deref, DerefMut,
+	},
+	ptr::NonNull,
+};
 use ::rse_cpp::{
-	AsObject, owned_vt_object_wrapper,
+	AsObject, VtObject, VtObjectMut, VtObjectPtr, owned_vt_object_wrapper, virtual_call,
 };
 
 use crate::{
 	cppdef::{
-		GameEventManager2Vt, INTERFACEVERSION_GAMEEVENTSMANAGER2,
+		GameEventManager2Vt, GameEventListener2Vt, GameEventVt,
+		INTERFACEVERSION_GAMEEVENTSMANAGER2,
 	},
+	EventListenerObject, RawEventListener,
+	GameEvent,
 	InterfaceOfFactory, AppSystemFactory,
 };
 
-pub trait GameEventManager2Impl: AsObject<GameEventManager2Vt> {}
+pub trait GameEventManager2Impl: AsObject<GameEventManager2Vt> {
+	/// Loads the event descriptions from the resource file at `path`,
+	/// returning the number of events that were loaded.
+	fn load_events_from_file(&self, path: &CStr) -> c_int {
+		unsafe { virtual_call!(self.as_object() => game_event_manager2.load_events_from_file(path.as_ptr())) }
+	}
+
+	/// Registers `listener` to be called when an event named `name` is fired.
+	///
+	/// # Safety
+	/// `listener` must be valid until it is removed with
+	/// [`remove_listener_raw`](GameEventManager2Impl::remove_listener_raw).
+	unsafe fn add_listener_raw(&self, listener: VtObjectMut<GameEventListener2Vt>, name: &CStr, server_side: bool) -> bool {
+		unsafe { virtual_call!(self.as_object() => game_event_manager2.add_listener(listener, name.as_ptr(), server_side)) }
+	}
+	/// Returns `true` if `listener` is registered for events named `name`.
+	fn find_listener_raw(&self, listener: VtObjectMut<GameEventListener2Vt>, name: &CStr) -> bool {
+		unsafe { virtual_call!(self.as_object() => game_event_manager2.find_listener(listener, name.as_ptr())) }
+	}
+	/// Removes `listener` from every event that it was registered for.
+	///
+	/// # Safety
+	/// `listener` must be a valid listener object.
+	unsafe fn remove_listener_raw(&self, listener: VtObjectMut<GameEventListener2Vt>) {
+		unsafe { virtual_call!(self.as_object() => game_event_manager2.remove_listener(listener)) }
+	}
+
+	/// Registers `listener` to be called when an event named `name` is fired,
+	/// returning a guard that removes it when dropped,
+	/// or `None` if there is no event named `name`.
+	///
+	/// # Safety
+	/// The guard must not outlive the event manager,
+	/// which holds for the interface that is provided by the engine.
+	unsafe fn add_listener<T: RawEventListener>(&self, name: &CStr, server_side: bool, listener: T) -> Option<EventListenerGuard<T>> {
+		let mut guard = EventListenerGuard {
+			manager: self.as_object().as_ptr(),
+			listener: NonNull::from(Box::leak(Box::new(EventListenerObject::new(listener)))),
+		};
+		guard.listen(name, server_side).then_some(guard)
+	}
+
+	/// Creates a new event named `name`,
+	/// returning `None` if there is no such event,
+	/// or if nothing listens for it and `force` is `false`.
+	///
+	/// # Safety
+	/// The event must not outlive the event manager,
+	/// which holds for the interface that is provided by the engine.
+	unsafe fn create_event(&self, name: &CStr, force: bool) -> Option<OwnedGameEvent> {
+		let event = unsafe { virtual_call!(self.as_object() => game_event_manager2.create_event(name.as_ptr(), force)) }?;
+		Some(OwnedGameEvent {
+			manager: self.as_object().as_ptr(),
+			event,
+		})
+	}
+	/// Creates a copy of `event`,
+	/// returning `None` if it wasn't created by the event manager.
+	///
+	/// # Safety
+	/// See [`create_event`](GameEventManager2Impl::create_event).
+	unsafe fn duplicate_event(&self, event: &GameEvent) -> Option<OwnedGameEvent> {
+		let event = unsafe { virtual_call!(self.as_object() => game_event_manager2.duplicate_event(event.as_object().as_ptr())) }?;
+		Some(OwnedGameEvent {
+			manager: self.as_object().as_ptr(),
+			event,
+		})
+	}
+}
 impl<T: ?Sized + AsObject<GameEventManager2Vt>> GameEventManager2Impl for T {}
 
 owned_vt_object_wrapper! {
@@ -22,3 +103,140 @@ unsafe impl ::rse_interface::Interface for GameEventManager2 {
 impl InterfaceOfFactory for GameEventManager2 {
 	type Factory = AppSystemFactory;
 }
+
+/// Event listener that is registered with an event manager,
+/// and is removed from it when the guard is dropped.
+///
+/// See [`GameEventManager2Impl::add_listener`].
+pub struct EventListenerGuard<T: RawEventListener> {
+	manager: VtObjectPtr<GameEventManager2Vt>,
+	listener: NonNull<EventListenerObject<T>>,
+}
+
+impl<T: RawEventListener> EventListenerGuard<T> {
+	fn manager(&self) -> &VtObject<GameEventManager2Vt> {
+		unsafe { VtObject::from_ptr_const(self.manager) }
+	}
+
+	/// Returns a pointer to the C++ listener object.
+	pub const fn as_ptr(&self) -> VtObjectMut<GameEventListener2Vt> {
+		self.listener.cast()
+	}
+
+	/// Additionally registers the listener for events named `name`.
+	pub fn listen(&mut self, name: &CStr, server_side: bool) -> bool {
+		unsafe { self.manager().add_listener_raw(self.as_ptr(), name, server_side) }
+	}
+
+	/// Returns `true` if the listener is registered for events named `name`.
+	pub fn is_listening(&self, name: &CStr) -> bool {
+		self.manager().find_listener_raw(self.as_ptr(), name)
+	}
+
+	/// Returns a reference to the listener.
+	///
+	/// # Safety
+	/// The engine calls the listener whenever an event that it is registered for is fired,
+	/// so no events may be fired while the returned reference is alive.
+	pub const unsafe fn as_inner(&self) -> &T {
+		unsafe { self.listener.as_ref().as_inner() }
+	}
+
+	/// Returns a mutable reference to the listener.
+	///
+	/// # Safety
+	/// See [`as_inner`](Self::as_inner).
+	pub const unsafe fn as_inner_mut(&mut self) -> &mut T {
+		unsafe { self.listener.as_mut().as_inner_mut() }
+	}
+
+	/// Removes the listener from the event manager and returns it.
+	pub fn into_inner(self) -> T {
+		let this = ManuallyDrop::new(self);
+		unsafe {
+			this.manager().remove_listener_raw(this.as_ptr());
+			(*Box::from_raw(this.listener.as_ptr())).into_inner()
+		}
+	}
+}
+
+impl<T: RawEventListener> Drop for EventListenerGuard<T> {
+	fn drop(&mut self) {
+		unsafe {
+			self.manager().remove_listener_raw(self.as_ptr());
+			drop(Box::from_raw(self.listener.as_ptr()));
+		}
+	}
+}
+
+/// Game event that was created by an event manager,
+/// and is freed when dropped unless it is fired.
+///
+/// See [`GameEventManager2Impl::create_event`].
+pub struct OwnedGameEvent {
+	manager: VtObjectPtr<GameEventManager2Vt>,
+	event: VtObjectMut<GameEventVt>,
+}
+
+impl OwnedGameEvent {
+	fn manager(&self) -> &VtObject<GameEventManager2Vt> {
+		unsafe { VtObject::from_ptr_const(self.manager) }
+	}
+
+	/// Creates a copy of the event, returning `None` if that failed.
+	pub fn duplicate(&self) -> Option<Self> {
+		// SAFETY: The copy is created by the same event manager, which outlives `self`.
+		unsafe { self.manager().duplicate_event(self) }
+	}
+
+	/// Fires the event, broadcasting it to clients.
+	///
+	/// The event manager frees the event afterwards, even if firing it fails.
+	pub fn fire(self) -> bool {
+		self.fire_event(false)
+	}
+
+	/// Fires the event without broadcasting it to clients.
+	///
+	/// See [`fire`](Self::fire).
+	pub fn fire_without_broadcast(self) -> bool {
+		self.fire_event(true)
+	}
+
+	/// Fires the event on the client side only.
+	///
+	/// See [`fire`](Self::fire).
+	pub fn fire_client_side(self) -> bool {
+		let this = ManuallyDrop::new(self);
+		unsafe { virtual_call!(this.manager() => game_event_manager2.fire_event_client_side(this.event)) }
+	}
+
+	fn fire_event(self, dont_broadcast: bool) -> bool {
+		let this = ManuallyDrop::new(self);
+		unsafe { virtual_call!(this.manager() => game_event_manager2.fire_event(this.event, dont_broadcast)) }
+	}
+
+	/// Returns the pointer to the C++ event object without freeing it.
+	pub fn into_raw(self) -> VtObjectMut<GameEventVt> {
+		ManuallyDrop::new(self).event
+	}
+}
+
+impl Deref for OwnedGameEvent {
+	type Target = GameEvent;
+	fn deref(&self) -> &Self::Target {
+		unsafe { VtObject::from_ptr_const(self.event) }.into()
+	}
+}
+
+impl DerefMut for OwnedGameEvent {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		unsafe { VtObject::from_ptr_mut(self.event) }.into()
+	}
+}
+
+impl Drop for OwnedGameEvent {
+	fn drop(&mut self) {
+		unsafe { virtual_call!(self.manager() => game_event_manager2.free_event(self.event)) }
+	}
+}
@@ -0,0 +1,212 @@
+use ::alloc::{
+	collections::BTreeMap,
+	ffi::CString,
+};
+use ::core::{
+	cell::RefCell,
+	ffi::{
+		CStr, c_char, c_int, c_float, c_void,
+	},
+	ptr::{
+		NonNull, null, null_mut,
+	},
+};
+use ::rse_cpp::{
+	VtObject, VtObjectMut, VtObjectPtr, new_vtable_self, this_to_self, virtual_call,
+};
+use ::rse_shared::{
+	cppdef::{
+		KeyValues as CKeyValues,
+		wchar_t,
+	},
+	keyvalues::Value,
+	WideCStr, WideCString,
+};
+
+use crate::{
+	cppdef::{
+		GameEventVt, GameEventVisitor2Vt,
+	},
+	GameEvent, RecordedEvent,
+};
+
+/// Game event implemented in Rust, with the fields of a [`RecordedEvent`].
+///
+/// Like `KeyValues` in the engine, numeric fields are converted between types when read,
+/// but only fields that are strings can be read as strings.
+///
+/// Pointers of `local` fields aren't kept, since they wouldn't be valid when the event is replayed,
+/// so `GetPtr` always returns null.
+#[repr(C)]
+pub struct GameEventObject {
+	vtable: NonNull<GameEventVt>,
+	event: RecordedEvent,
+	/// Wide strings that were last returned by `GetWString` for each key,
+	/// which stay alive until the key's value changes.
+	wide_strings: RefCell<BTreeMap<CString, WideCString>>,
+}
+
+impl GameEventObject {
+	pub const fn new(event: RecordedEvent) -> Self {
+		Self {
+			vtable: unsafe { NonNull::new_unchecked(Self::VTABLE as *const _ as *mut _) },
+			event,
+			wide_strings: RefCell::new(BTreeMap::new()),
+		}
+	}
+
+	pub fn as_event(&self) -> &GameEvent {
+		unsafe { VtObject::from_ptr_const(NonNull::from(self).cast()) }.into()
+	}
+
+	pub fn as_event_mut(&mut self) -> &mut GameEvent {
+		unsafe { VtObject::from_ptr_mut(NonNull::from(self).cast()) }.into()
+	}
+
+	pub const fn as_inner(&self) -> &RecordedEvent {
+		&self.event
+	}
+
+	pub fn into_inner(self) -> RecordedEvent {
+		self.event
+	}
+
+	fn get<'a>(this: VtObjectPtr<GameEventVt>, key_name: *const c_char) -> Option<&'a Value> {
+		let this = this_to_self!(ref this);
+		this.event.data.get(unsafe { CStr::from_ptr(key_name) })
+	}
+
+	fn set(this: VtObjectPtr<GameEventVt>, key_name: *const c_char, value: Value) {
+		let this = this_to_self!(mut this);
+		this.event.data.set(unsafe { CStr::from_ptr(key_name) }, value)
+	}
+
+	const VTABLE: &GameEventVt = &new_vtable_self!(GameEventVt {
+		destructor,
+		#[cfg(not(windows))]
+		destructor_2,
+		get_name,
+		is_reliable,
+		is_local,
+		is_empty,
+		get_bool,
+		get_int,
+		get_float,
+		get_string,
+		set_bool,
+		set_int,
+		set_float,
+		set_string,
+		get_uint64,
+		get_w_string,
+		get_ptr,
+		set_uint64,
+		set_w_string,
+		set_ptr,
+		get_data_keys,
+		for_event_data
+	});
+
+	::rse_cpp::vtable_methods! {
+		this: VtObjectPtr<GameEventVt>;
+		fn destructor() {
+			unsafe { this.cast::<Self>().drop_in_place() }
+		}
+		#[cfg(not(windows))]
+		fn destructor_2() {
+			unsafe { this.cast::<Self>().drop_in_place() }
+		}
+		fn get_name() -> *const c_char {
+			this_to_self!(ref this).event.name().as_ptr()
+		}
+		fn is_reliable() -> bool {
+			this_to_self!(ref this).event.reliable
+		}
+		fn is_local() -> bool {
+			this_to_self!(ref this).event.local
+		}
+		fn is_empty(key_name: *const c_char) -> bool {
+			Self::get(this, key_name).is_none()
+		}
+		fn get_bool(key_name: *const c_char, default_value: bool) -> bool {
+			Self::get(this, key_name).and_then(Value::to_bool).unwrap_or(default_value)
+		}
+		fn get_int(key_name: *const c_char, default_value: c_int) -> c_int {
+			Self::get(this, key_name).and_then(Value::to_int).unwrap_or(default_value)
+		}
+		fn get_float(key_name: *const c_char, default_value: c_float) -> c_float {
+			Self::get(this, key_name).and_then(Value::to_float).unwrap_or(default_value)
+		}
+		fn get_string(key_name: *const c_char, default_value: *const c_char) -> *const c_char {
+			Self::get(this, key_name).and_then(Value::as_c_str).map_or(default_value, CStr::as_ptr)
+		}
+		fn set_bool(key_name: *const c_char, value: bool) {
+			Self::set(this, key_name, Value::Int(value as _))
+		}
+		fn set_int(key_name: *const c_char, value: c_int) {
+			Self::set(this, key_name, Value::Int(value))
+		}
+		fn set_float(key_name: *const c_char, value: c_float) {
+			Self::set(this, key_name, Value::Float(value))
+		}
+		fn set_string(key_name: *const c_char, value: *const c_char) {
+			Self::set(this, key_name, Value::String(unsafe { CStr::from_ptr(value) }.into()))
+		}
+		fn get_uint64(key_name: *const c_char, default_value: u64) -> u64 {
+			Self::get(this, key_name).and_then(Value::to_uint64).unwrap_or(default_value)
+		}
+		fn get_w_string(key_name: *const c_char, default_value: *const wchar_t) -> *const wchar_t {
+			let this = this_to_self!(ref this);
+			let Some(kv) = this.event.data.find_key(unsafe { CStr::from_ptr(key_name) }) else {
+				return default_value
+			};
+			let Value::WString(ref s) = *kv.value() else {
+				return default_value
+			};
+			let mut wide_strings = this.wide_strings.borrow_mut();
+			if let Some(wide) = wide_strings.get(kv.name()).filter(move |wide| wide.chars_lossy().eq(s.chars())) {
+				return wide.as_ptr()
+			}
+			let Ok(wide) = WideCString::new(s) else {
+				return default_value
+			};
+			let ptr = wide.as_ptr();
+			wide_strings.insert(kv.name().into(), wide);
+			ptr
+		}
+		fn get_ptr(_key_name: *const c_char) -> *const c_void {
+			null()
+		}
+		fn set_uint64(key_name: *const c_char, value: u64) {
+			Self::set(this, key_name, Value::Uint64(value))
+		}
+		fn set_w_string(key_name: *const c_char, value: *const wchar_t) {
+			Self::set(this, key_name, Value::WString(unsafe { WideCStr::from_ptr(value) }.to_string_lossy()))
+		}
+		fn set_ptr(_key_name: *const c_char, _value: *const c_void) {
+		}
+		fn get_data_keys() -> *mut CKeyValues {
+			null_mut()
+		}
+		fn for_event_data(visitor: VtObjectMut<GameEventVisitor2Vt>) {
+			let visitor = unsafe { VtObject::from_ptr_const(visitor) };
+			for kv in this_to_self!(ref this).event.data.subkeys() {
+				let name = kv.name().as_ptr();
+				let go_on = unsafe { match *kv.value() {
+					Value::String(ref s) => virtual_call!(visitor => visit_string(name, s.as_ptr())),
+					Value::Int(i) => virtual_call!(visitor => visit_int(name, i)),
+					Value::Float(f) => virtual_call!(visitor => visit_float(name, f)),
+					Value::Uint64(u) => virtual_call!(visitor => visit_uint64(name, u)),
+					Value::WString(ref s) => match WideCString::new(s) {
+						Ok(s) => virtual_call!(visitor => visit_w_string(name, s.as_ptr())),
+						Err(..) => true,
+					},
+					Value::Ptr(..) | Value::Subkeys(..) | Value::Color(..) => true,
+				} };
+				if !go_on {
+					break
+				}
+			}
+		}
+	}
+}
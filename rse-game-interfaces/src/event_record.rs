@@ -0,0 +1,188 @@
+use ::alloc::{
+	ffi::CString,
+	vec::Vec,
+};
+use ::core::ffi::{
+	CStr, c_float,
+};
+use ::rse_shared::keyvalues::{
+	BinaryError, KeyValues,
+};
+
+use crate::{
+	GameEvent, GameEventImpl, GameEventObject,
+	RawEventListener,
+};
+
+/// Error that occured while reading an event log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ::thiserror::Error)]
+pub enum EventLogError {
+	#[error(transparent)]
+	Binary(#[from] BinaryError),
+	#[error("entry {0} of the log isn't an event")]
+	InvalidEvent(usize),
+}
+
+/// Game event that was recorded, with the values of its fields.
+///
+/// See also [`GameEventObject`], which replays it as a [`GameEvent`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedEvent {
+	/// Server time, in seconds, at which the event was recorded.
+	pub time: c_float,
+	pub reliable: bool,
+	pub local: bool,
+	/// Tree named after the event, with a subkey for every field of the event.
+	pub data: KeyValues,
+}
+
+impl RecordedEvent {
+	/// Returns a new reliable, networked event named `name` with no fields.
+	pub fn new(name: impl Into<CString>, time: c_float) -> Self {
+		Self {
+			time,
+			reliable: true,
+			local: false,
+			data: KeyValues::new(name),
+		}
+	}
+
+	/// Records the name and fields of `event` at the server time `time`.
+	///
+	/// `local` fields aren't recorded, since their pointers wouldn't be valid when replayed.
+	pub fn from_event(event: &GameEvent, time: c_float) -> Self {
+		Self {
+			time,
+			reliable: event.is_reliable(),
+			local: event.is_local(),
+			data: event.to_key_values(),
+		}
+	}
+
+	pub fn name(&self) -> &CStr {
+		self.data.name()
+	}
+
+	/// Calls `listener` with the event, as if it was fired by the engine.
+	pub fn replay_to<L: ?Sized + RawEventListener>(&self, listener: &mut L) {
+		let event = GameEventObject::new(self.clone());
+		listener.fire_game_event(event.as_event())
+	}
+
+	/// Returns the tree that represents the event in an event log.
+	pub fn to_key_values(&self) -> KeyValues {
+		let data = self.data.subkeys().iter().cloned().fold(KeyValues::new(c"data"), KeyValues::with_subkey);
+		KeyValues::new(self.name())
+			.with(c"time", self.time)
+			.with(c"reliable", self.reliable)
+			.with(c"local", self.local)
+			.with_subkey(data)
+	}
+
+	/// Reads an event from a tree returned by [`to_key_values`](Self::to_key_values).
+	pub fn from_key_values(kv: &KeyValues) -> Option<Self> {
+		let fields = kv.find_key(c"data")?;
+		let data = fields.subkeys().iter().cloned().fold(KeyValues::new(kv.name()), KeyValues::with_subkey);
+		Some(Self {
+			time: kv.get_float(c"time")?,
+			reliable: kv.get_bool(c"reliable").unwrap_or(true),
+			local: kv.get_bool(c"local").unwrap_or(false),
+			data,
+		})
+	}
+
+	/// Returns `events` in the binary `KeyValues` format,
+	/// which keeps the types of the fields.
+	pub fn write_log(events: &[Self]) -> Vec<u8> {
+		let keys: Vec<_> = events.iter().map(Self::to_key_values).collect();
+		let mut out = Vec::new();
+		KeyValues::write_binary_list(&keys, &mut out);
+		out
+	}
+
+	/// Reads events that were written with [`write_log`](Self::write_log).
+	pub fn parse_log(data: &[u8]) -> Result<Vec<Self>, EventLogError> {
+		KeyValues::parse_binary(data)?
+			.iter()
+			.enumerate()
+			.map(move |(i, kv)| Self::from_key_values(kv).ok_or(EventLogError::InvalidEvent(i)))
+			.collect()
+	}
+}
+
+/// Calls `listener` with every event of `events` in order, as if they were fired by the engine.
+pub fn replay_events<'a, L: ?Sized + RawEventListener>(
+	events: impl IntoIterator<Item = &'a RecordedEvent>, listener: &mut L,
+) {
+	for event in events {
+		event.replay_to(listener)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ::rse_shared::{
+		keyvalues::Value,
+		WideCStr,
+	};
+
+	use super::*;
+
+	#[derive(Default)]
+	struct Recorder(Vec<RecordedEvent>);
+	impl RawEventListener for Recorder {
+		fn fire_game_event(&mut self, event: &GameEvent) {
+			self.0.push(RecordedEvent::from_event(event, 0.0))
+		}
+	}
+
+	#[test]
+	fn replay() {
+		let mut event = RecordedEvent::new(c"player_death", 12.5);
+		event.data.set(c"userid", 3);
+		event.data.set(c"headshot", Value::Int(1));
+		event.data.set(c"weapon", c"shotgun");
+		event.data.set(c"steamid", Value::Uint64(76561197960287930));
+		event.data.set(c"message", Value::WString("héllo 🦀".into()));
+
+		let mut object = GameEventObject::new(event.clone());
+		let game_event = object.as_event_mut();
+		assert_eq!(game_event.name(), c"player_death");
+		assert_eq!(game_event.get_int_or(c"userid", 0), 3);
+		assert_eq!(game_event.get_float_or(c"userid", 0.0), 3.0);
+		assert!(game_event.get_bool_or(c"headshot", false));
+		assert_eq!(game_event.get_string(c"weapon"), Some(c"shotgun"));
+		assert_eq!(game_event.get_string(c"userid"), None);
+		assert_eq!(game_event.get_uint64_or(c"steamid", 0), 76561197960287930);
+		assert_eq!(
+			game_event.get_wide_string(c"message").map(WideCStr::to_string_lossy).as_deref(),
+			Some("héllo 🦀"),
+		);
+		let message = game_event.get_wide_string(c"message").map(WideCStr::as_ptr);
+		assert_eq!(game_event.get_wide_string(c"MESSAGE").map(WideCStr::as_ptr), message);
+		assert!(game_event.is_key_empty(c"attacker"));
+		game_event.set_int(c"attacker", 5);
+		assert_eq!(game_event.get_int_or(c"attacker", 0), 5);
+
+		let local = 0;
+		unsafe { game_event.set_ptr(c"entity", (&raw const local).cast()) };
+		assert!(game_event.get_ptr(c"entity").is_null());
+
+		let mut with_ptr = event.clone();
+		with_ptr.data.set(c"entity", Value::Ptr(16 as _));
+		let replayed = GameEventObject::new(with_ptr);
+		assert!(replayed.as_event().get_ptr(c"entity").is_null());
+		assert_eq!(RecordedEvent::from_event(replayed.as_event(), 0.0).data, event.data);
+
+		let mut recorder = Recorder::default();
+		replay_events([&event], &mut recorder);
+		assert_eq!(recorder.0[0].data, event.data);
+
+		let log = RecordedEvent::write_log(&[event.clone(), RecordedEvent::new(c"round_start", 13.0)]);
+		let parsed = RecordedEvent::parse_log(&log).unwrap();
+		assert_eq!(parsed.len(), 2);
+		assert_eq!(parsed[0], event);
+		assert_eq!(parsed[1].name(), c"round_start");
+		assert!(parsed[1].data.subkeys().is_empty());
+	}
+}
@@ -1,14 +1,25 @@
 use ::core::{
 	ffi::{
-		CStr, c_int, c_float,
+		CStr, c_char, c_int, c_float, c_void,
+	},
+	ptr::{
+		NonNull, null,
 	},
-	ptr::null,
 };
 use ::rse_cpp::{
-	AsObject, virtual_call, vt_object_wrapper,
+	AsObject, VtObjectPtr, virtual_call, vt_object_wrapper, new_vtable_self, this_to_self,
+};
+use ::rse_shared::{
+	cppdef::wchar_t,
+	keyvalues::{
+		KeyValues, Value,
+	},
+	WideCStr,
 };
 
-use crate::cppdef::GameEventVt;
+use crate::cppdef::{
+	GameEventVt, GameEventVisitor2Vt,
+};
 
 pub trait GameEventImpl: AsObject<GameEventVt> {
 	fn name(&self) -> &CStr {
@@ -32,7 +43,7 @@ pub trait GameEventImpl: AsObject<GameEventVt> {
 	fn get_float_or(&self, key: &CStr, default: c_float) -> c_float {
 		unsafe { virtual_call!(self.as_object() => get_float(key.as_ptr(), default)) }
 	}
-	fn get_string_or(&self, key: &CStr, default: &CStr) -> &CStr {
+	fn get_string_or<'a>(&'a self, key: &CStr, default: &'a CStr) -> &'a CStr {
 		let ptr = unsafe { virtual_call!(self.as_object() => get_string(key.as_ptr(), default.as_ptr())) };
 		unsafe { CStr::from_ptr(ptr) }
 	}
@@ -44,7 +55,25 @@ pub trait GameEventImpl: AsObject<GameEventVt> {
 			None
 		}
 	}
-	// TODO: `IGameEvent::Get*`.
+	fn get_uint64_or(&self, key: &CStr, default: u64) -> u64 {
+		unsafe { virtual_call!(self.as_object() => get_uint64(key.as_ptr(), default)) }
+	}
+	fn get_wide_string_or<'a>(&'a self, key: &CStr, default: &'a WideCStr) -> &'a WideCStr {
+		let ptr = unsafe { virtual_call!(self.as_object() => get_w_string(key.as_ptr(), default.as_ptr())) };
+		unsafe { WideCStr::from_ptr(ptr) }
+	}
+	fn get_wide_string(&self, key: &CStr) -> Option<&WideCStr> {
+		let ptr = unsafe { virtual_call!(self.as_object() => get_w_string(key.as_ptr(), null())) };
+		if !ptr.is_null() {
+			unsafe { Some(WideCStr::from_ptr(ptr)) }
+		} else {
+			None
+		}
+	}
+	/// Returns the pointer value of `key`, which is only set for events that are fired locally.
+	fn get_ptr(&self, key: &CStr) -> *const c_void {
+		unsafe { virtual_call!(self.as_object() => get_ptr(key.as_ptr())) }
+	}
 
 	fn set_bool(&mut self, key: &CStr, value: bool) {
 		unsafe { virtual_call!(self.as_object() => set_bool(key.as_ptr(), value)) }
@@ -58,10 +87,99 @@ pub trait GameEventImpl: AsObject<GameEventVt> {
 	fn set_string(&mut self, key: &CStr, value: &CStr) {
 		unsafe { virtual_call!(self.as_object() => set_string(key.as_ptr(), value.as_ptr())) }
 	}
-	// TODO: `IGameEvent::Set*`.
+	fn set_uint64(&mut self, key: &CStr, value: u64) {
+		unsafe { virtual_call!(self.as_object() => set_uint64(key.as_ptr(), value)) }
+	}
+	fn set_wide_string(&mut self, key: &CStr, value: &WideCStr) {
+		unsafe { virtual_call!(self.as_object() => set_w_string(key.as_ptr(), value.as_ptr())) }
+	}
+	/// Sets the pointer value of `key`, which is not networked.
+	///
+	/// # Safety
+	/// `value` must stay valid for as long as listeners may read it.
+	unsafe fn set_ptr(&mut self, key: &CStr, value: *const c_void) {
+		unsafe { virtual_call!(self.as_object() => set_ptr(key.as_ptr(), value)) }
+	}
+
+	/// Calls `f` with the name and value of every field of the event, in order.
+	///
+	/// Booleans are passed as [`Value::Int`].
+	/// `local` fields are skipped, since their pointers are only valid while the event is being fired.
+	fn visit_fields<F: FnMut(&CStr, Value)>(&self, f: F) {
+		let mut visitor = FieldVisitor::new(f);
+		let visitor = NonNull::from(&mut visitor).cast();
+		unsafe { virtual_call!(self.as_object() => for_event_data(visitor)) }
+	}
+
+	/// Returns a tree named after the event, with a subkey for every field of the event.
+	///
+	/// See [`visit_fields`](GameEventImpl::visit_fields).
+	fn to_key_values(&self) -> KeyValues {
+		let mut kv = KeyValues::new(self.name());
+		self.visit_fields(|name, value| {
+			kv.add_subkey(KeyValues::with_value(name, value));
+		});
+		kv
+	}
 }
 impl<T: ?Sized + AsObject<GameEventVt>> GameEventImpl for T {}
 
+#[repr(C)]
+struct FieldVisitor<F> {
+	vtable: NonNull<GameEventVisitor2Vt>,
+	f: F,
+}
+
+impl<F: FnMut(&CStr, Value)> FieldVisitor<F> {
+	const fn new(f: F) -> Self {
+		Self {
+			vtable: unsafe { NonNull::new_unchecked(Self::VTABLE as *const _ as *mut _) },
+			f,
+		}
+	}
+
+	fn visit(this: VtObjectPtr<GameEventVisitor2Vt>, name: *const c_char, value: Value) -> bool {
+		let name = unsafe { CStr::from_ptr(name) };
+		(this_to_self!(mut this).f)(name, value);
+		true
+	}
+
+	const VTABLE: &GameEventVisitor2Vt = &new_vtable_self!(GameEventVisitor2Vt {
+		visit_local,
+		visit_string,
+		visit_float,
+		visit_int,
+		visit_uint64,
+		visit_w_string,
+		visit_bool
+	});
+
+	::rse_cpp::vtable_methods! {
+		this: VtObjectPtr<GameEventVisitor2Vt>;
+		fn visit_local(_name: *const c_char, _value: *const c_void) -> bool {
+			true
+		}
+		fn visit_string(name: *const c_char, value: *const c_char) -> bool {
+			Self::visit(this, name, Value::String(unsafe { CStr::from_ptr(value) }.into()))
+		}
+		fn visit_float(name: *const c_char, value: c_float) -> bool {
+			Self::visit(this, name, Value::Float(value))
+		}
+		fn visit_int(name: *const c_char, value: c_int) -> bool {
+			Self::visit(this, name, Value::Int(value))
+		}
+		fn visit_uint64(name: *const c_char, value: u64) -> bool {
+			Self::visit(this, name, Value::Uint64(value))
+		}
+		fn visit_w_string(name: *const c_char, value: *const wchar_t) -> bool {
+			Self::visit(this, name, Value::WString(unsafe { WideCStr::from_ptr(value) }.to_string_lossy()))
+		}
+		fn visit_bool(name: *const c_char, value: bool) -> bool {
+			Self::visit(this, name, Value::Int(value as _))
+		}
+	}
+}
+
 vt_object_wrapper! {
 	pub struct GameEvent for GameEventVt;
 }
@@ -0,0 +1,23 @@
+use ::core::ffi::CStr;
+use ::rse_cpp::owned_vt_object_wrapper;
+use ::rse_shared::cppdef::{
+	KeyValuesSystemVt, KEYVALUESSYSTEM_INTERFACE_VERSION,
+};
+
+use crate::{
+	InterfaceOfFactory, AppSystemFactory,
+};
+
+pub use ::rse_shared::keyvalues::{
+	KeyValuesSystemImpl, EngineKeyValues,
+};
+
+owned_vt_object_wrapper! {
+	pub struct KeyValuesSystem for KeyValuesSystemVt;
+}
+unsafe impl ::rse_interface::Interface for KeyValuesSystem {
+	const IDENTIFIER: &CStr = KEYVALUESSYSTEM_INTERFACE_VERSION;
+}
+impl InterfaceOfFactory for KeyValuesSystem {
+	type Factory = AppSystemFactory;
+}
@@ -1,15 +1,24 @@
 #![no_std]
 
+extern crate alloc;
+
 pub mod cppdef;
 
 pub mod cvar;
+pub mod user_messages;
 
 mod engine_server;
 pub use engine_server::*;
+mod engine_trace;
+pub use engine_trace::*;
 mod event_listener;
 pub use event_listener::*;
 mod event_manager;
 pub use event_manager::*;
+mod event_object;
+pub use event_object::*;
+mod event_record;
+pub use event_record::*;
 mod event;
 pub use event::*;
 mod file_system;
@@ -18,7 +27,15 @@ mod game_event;
 pub use game_event::*;
 mod interface_factories;
 pub use interface_factories::*;
+mod key_values_system;
+pub use key_values_system::*;
 mod player_info;
 pub use player_info::*;
+mod pvs;
+pub use pvs::*;
+mod recipient_filter;
+pub use recipient_filter::*;
 mod server_game_dll;
 pub use server_game_dll::*;
+mod user_message;
+pub use user_message::*;
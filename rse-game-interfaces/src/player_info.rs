@@ -1,12 +1,18 @@
-use ::core::ffi::{
-	CStr, c_int,
+use ::core::{
+	ffi::{
+		CStr, c_int,
+	},
+	ptr::NonNull,
 };
 use ::rse_cpp::{
 	AsObject, virtual_call, vt_object_wrapper, owned_vt_object_wrapper,
-	VtObject, VtObjectWrapper,
+	VtObject, VtObjectPtr, VtObjectWrapper,
+	new_vtable_self, VTablePtr,
 };
 use ::rse_shared::{
-	cppdef::GlobalVars,
+	cppdef::{
+		GlobalVars, SteamId,
+	},
 	ServerEdict,
 };
 use ::rse_interface::Interface;
@@ -18,13 +24,33 @@ use crate::{
 	cppdef::{
 		PlayerInfoManagerVt, PlayerInfoVt, PlayerInfo2Vt,
 		INTERFACEVERSION_PLAYERINFOMANAGER,
-		BotManagerVt, BotControllerVt,
+		BotManagerVt, BotControllerVt, BotCmdVt,
 		INTERFACEVERSION_PLAYERBOTMANAGER,
 	},
 	InterfaceOfFactory, GameServerFactory,
 };
 
-pub use crate::cppdef::BotCmd;
+pub use crate::cppdef::{
+	BotCmd, BotCmdExt, Buttons,
+};
+
+/// Provides the v-table of [`BotCmd`]s created in Rust.
+struct RustBotCmd;
+impl RustBotCmd {
+	const VTABLE: &BotCmdVt = &new_vtable_self!(BotCmdVt {
+		destructor,
+		#[cfg(not(windows))]
+		destructor_2
+	});
+
+	::rse_cpp::vtable_methods! {
+		this: VtObjectPtr<BotCmdVt>;
+		// Commands are only ever borrowed by the game DLL.
+		fn destructor() {}
+		#[cfg(not(windows))]
+		fn destructor_2() {}
+	}
+}
 
 pub trait PlayerInfoManagerImpl: AsObject<PlayerInfoManagerVt> {
 	fn player_info_mut<'a>(&self, edict: &'a mut ServerEdict) -> Option<&'a mut PlayerInfo2> {
@@ -61,6 +87,11 @@ pub trait PlayerInfoImpl: AsObject<PlayerInfoVt> {
 	fn network_id(&self) -> &CStr {
 		unsafe { CStr::from_ptr(virtual_call!(self.as_object() => get_network_id_string())) }
 	}
+	/// Returns the player's SteamID parsed from [`network_id`](Self::network_id),
+	/// or `None` if it isn't a SteamID (e.g. for bots and LAN players).
+	fn steam_id(&self) -> Option<SteamId> {
+		self.network_id().to_str().ok()?.parse().ok()
+	}
 	/// Returns the team the player is on.
 	fn team_index(&self) -> c_int {
 		unsafe { virtual_call!(self.as_object() => get_team_index()) }
@@ -203,8 +234,54 @@ pub trait BotManagerImpl: AsObject<BotManagerVt> {
 }
 impl<T: ?Sized + AsObject<BotManagerVt>> BotManagerImpl for T {}
 
+/// Safe interface to `IBotController`.
 pub trait BotControllerImpl: AsObject<BotControllerVt> {
-	// TODO: `IBotController` methods.
+	/// Teleports the bot to `origin`.
+	fn set_abs_origin(&self, origin: &Vector) {
+		let mut origin = *origin;
+		unsafe { virtual_call!(self.as_object() => set_abs_origin(NonNull::from(&mut origin))) }
+	}
+	/// Sets the absolute angles (orientation) of the bot.
+	fn set_abs_angles(&self, angles: &QAngle) {
+		let mut angles = *angles;
+		unsafe { virtual_call!(self.as_object() => set_abs_angles(NonNull::from(&mut angles))) }
+	}
+	/// Sets the origin of the bot relative to its parent.
+	fn set_local_origin(&self, origin: &Vector) {
+		unsafe { virtual_call!(self.as_object() => set_local_origin(NonNull::from(origin))) }
+	}
+	/// Returns the origin of the bot relative to its parent.
+	fn local_origin(&self) -> Vector {
+		unsafe { virtual_call!(self.as_object() => get_local_origin()) }
+	}
+	/// Sets the angles of the bot relative to its parent.
+	fn set_local_angles(&self, angles: &QAngle) {
+		unsafe { virtual_call!(self.as_object() => set_local_angles(NonNull::from(angles))) }
+	}
+	/// Returns the angles of the bot relative to its parent.
+	fn local_angles(&self) -> QAngle {
+		unsafe { virtual_call!(self.as_object() => get_local_angles()) }
+	}
+
+	/// Removes all items of the bot, including the HEV suit if `remove_suit` is `true`.
+	fn remove_all_items(&self, remove_suit: bool) {
+		unsafe { virtual_call!(self.as_object() => remove_all_items(remove_suit)) }
+	}
+	/// Switches the bot to the weapon named `weapon_name`, like `weapon_crowbar`.
+	fn set_active_weapon(&self, weapon_name: &CStr) {
+		unsafe { virtual_call!(self.as_object() => set_active_weapon(weapon_name.as_ptr())) }
+	}
+	/// Returns `true` if any of the entity flags (`EFL_*`) in `mask` are set on the bot.
+	fn is_eflag_set(&self, mask: c_int) -> bool {
+		unsafe { virtual_call!(self.as_object() => is_eflag_set(mask)) }
+	}
+	/// Runs a single movement command for the bot.
+	/// 
+	/// This should be called for every bot on every tick.
+	fn run_player_move(&self, cmd: &BotCmdExt) {
+		let mut cmd = BotCmd::new(VTablePtr::from_ref(RustBotCmd::VTABLE), *cmd);
+		unsafe { virtual_call!(self.as_object() => run_player_move(&mut cmd)) }
+	}
 }
 impl<T: ?Sized + AsObject<BotControllerVt>> BotControllerImpl for T {}
 
@@ -0,0 +1,97 @@
+use ::core::{
+	ffi::c_int,
+	fmt,
+};
+
+/// Maximum number of visibility clusters in a map.
+pub const MAX_MAP_CLUSTERS: usize = 65536;
+/// Maximum number of areas in a map.
+pub const MAX_MAP_AREAS: usize = 256;
+
+/// Size of a [`Pvs`] in bytes.
+pub const PVS_SIZE: usize = MAX_MAP_CLUSTERS / 8;
+/// Size of [`AreaBits`] in bytes.
+pub const MAX_AREA_STATE_BYTES: usize = MAX_MAP_AREAS / 8;
+
+/// Potentially Visible Set, which is the set of visibility clusters that can be seen from a cluster.
+///
+/// See [`VEngineServerImpl::pvs_for_cluster`](crate::VEngineServerImpl::pvs_for_cluster).
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Pvs {
+	bits: [u8; PVS_SIZE],
+	len: usize,
+}
+
+impl Pvs {
+	/// Returns an empty set.
+	pub const fn new() -> Self {
+		Self {
+			bits: [0; PVS_SIZE],
+			len: 0,
+		}
+	}
+
+	/// Returns the bytes of the set that are used by the current map.
+	pub fn as_bytes(&self) -> &[u8] {
+		&self.bits[..self.len]
+	}
+
+	/// Returns the whole buffer of the set, to be filled in by the engine.
+	pub(crate) const fn buffer_mut(&mut self) -> &mut [u8; PVS_SIZE] {
+		&mut self.bits
+	}
+
+	/// Sets the number of bytes of the set that are used by the current map.
+	pub(crate) const fn set_len(&mut self, len: usize) {
+		self.len = if len > PVS_SIZE { PVS_SIZE } else { len };
+	}
+
+	/// Returns `true` if the cluster `cluster` is in the set.
+	pub fn contains_cluster(&self, cluster: c_int) -> bool {
+		let Ok(cluster) = usize::try_from(cluster) else { return false };
+		self.as_bytes().get(cluster >> 3).is_some_and(move |&byte| byte & (1 << (cluster & 7)) != 0)
+	}
+
+	/// Adds all clusters of `other` to `self`.
+	pub fn union_with(&mut self, other: &Self) {
+		self.len = self.len.max(other.len);
+		for (byte, other) in self.bits.iter_mut().zip(other.as_bytes()) {
+			*byte |= *other;
+		}
+	}
+
+	/// Returns the number of clusters in the set.
+	pub fn count(&self) -> usize {
+		self.as_bytes().iter().map(move |byte| byte.count_ones() as usize).sum()
+	}
+}
+
+impl Default for Pvs {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl fmt::Debug for Pvs {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Pvs")
+			.field("len", &self.len)
+			.field("count", &self.count())
+			.finish()
+	}
+}
+
+/// Set of areas that can be seen from an area, taking the states of area portals into account.
+///
+/// See [`VEngineServerImpl::area_bits`](crate::VEngineServerImpl::area_bits).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct AreaBits(pub [u8; MAX_AREA_STATE_BYTES]);
+
+impl AreaBits {
+	/// Returns `true` if the area `area` is in the set.
+	pub fn contains(&self, area: c_int) -> bool {
+		let Ok(area) = usize::try_from(area) else { return false };
+		self.0.get(area >> 3).is_some_and(move |&byte| byte & (1 << (area & 7)) != 0)
+	}
+}
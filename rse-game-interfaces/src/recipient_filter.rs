@@ -0,0 +1,230 @@
+use ::core::{
+	ffi::c_int,
+	fmt,
+	ptr::NonNull,
+};
+use ::rse_cpp::{
+	AsObject, VtObject, VtObjectMut, VtObjectPtr, virtual_call,
+	new_vtable_self, this_to_self,
+	WithVTable, VTablePtr,
+};
+
+use crate::cppdef::{
+	RecipientFilterVt, PlayerBitVec,
+	ABSOLUTE_PLAYER_LIMIT,
+};
+
+/// Safe interface to `IRecipientFilter`.
+pub trait RecipientFilterImpl: AsObject<RecipientFilterVt> {
+	/// Returns `true` if messages sent to the recipients are sent on the reliable channel.
+	fn is_reliable(&self) -> bool {
+		unsafe { virtual_call!(self.as_object() => is_reliable()) }
+	}
+	/// Returns `true` if messages sent to the recipients are part of the signon data.
+	fn is_init_message(&self) -> bool {
+		unsafe { virtual_call!(self.as_object() => is_init_message()) }
+	}
+	/// Returns the number of recipients.
+	fn recipient_count(&self) -> usize {
+		unsafe { virtual_call!(self.as_object() => get_recipient_count()).max(0) as _ }
+	}
+	/// Returns the entity index of the recipient in `slot`.
+	fn recipient_index(&self, slot: usize) -> Option<c_int> {
+		if slot < self.recipient_count() {
+			Some(unsafe { virtual_call!(self.as_object() => get_recipient_index(slot as _)) })
+		} else {
+			None
+		}
+	}
+}
+impl<T: ?Sized + AsObject<RecipientFilterVt>> RecipientFilterImpl for T {}
+
+/// C++ object that implements `IRecipientFilter` with a list of player entity indices.
+#[repr(C)]
+pub struct RecipientFilter {
+	object: WithVTable<RecipientFilterVt, Recipients>,
+}
+
+#[derive(Clone)]
+struct Recipients {
+	reliable: bool,
+	init_message: bool,
+	count: usize,
+	players: [c_int; ABSOLUTE_PLAYER_LIMIT],
+}
+
+impl fmt::Debug for RecipientFilter {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RecipientFilter")
+			.field("reliable", &self.object.data.reliable)
+			.field("init_message", &self.object.data.init_message)
+			.field("recipients", &self.recipients())
+			.finish()
+	}
+}
+
+impl Clone for RecipientFilter {
+	fn clone(&self) -> Self {
+		Self {
+			object: WithVTable::new(self.object.vtable, self.object.data.clone()),
+		}
+	}
+}
+
+impl Default for RecipientFilter {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl RecipientFilter {
+	/// Returns a new filter with no recipients, for unreliable messages.
+	pub const fn new() -> Self {
+		Self {
+			object: WithVTable::new(
+				VTablePtr::from_ref(Self::VTABLE),
+				Recipients {
+					reliable: false,
+					init_message: false,
+					count: 0,
+					players: [0; ABSOLUTE_PLAYER_LIMIT],
+				},
+			),
+		}
+	}
+
+	/// Returns a new filter with the player with the entity index `index` as the only recipient.
+	pub fn single(index: c_int) -> Self {
+		let mut filter = Self::new();
+		filter.add_recipient(index);
+		filter
+	}
+
+	/// Returns a new filter with the players with the given entity indices as recipients.
+	pub fn with_recipients(indices: impl IntoIterator<Item = c_int>) -> Self {
+		let mut filter = Self::new();
+		filter.add_recipients(indices);
+		filter
+	}
+
+	/// Returns `self` marked as reliable.
+	pub const fn reliable(mut self) -> Self {
+		self.object.data.reliable = true;
+		self
+	}
+
+	/// Returns `self` marked as an init message.
+	pub const fn init_message(mut self) -> Self {
+		self.object.data.init_message = true;
+		self
+	}
+
+	pub const fn set_reliable(&mut self, reliable: bool) {
+		self.object.data.reliable = reliable;
+	}
+
+	pub const fn set_init_message(&mut self, init_message: bool) {
+		self.object.data.init_message = init_message;
+	}
+
+	/// Returns the entity indices of the recipients.
+	pub fn recipients(&self) -> &[c_int] {
+		&self.object.data.players[..self.object.data.count]
+	}
+
+	pub const fn len(&self) -> usize {
+		self.object.data.count
+	}
+
+	pub const fn is_empty(&self) -> bool {
+		self.object.data.count == 0
+	}
+
+	/// Returns `true` if the player with the entity index `index` is a recipient.
+	pub fn contains(&self, index: c_int) -> bool {
+		self.recipients().contains(&index)
+	}
+
+	/// Adds the player with the entity index `index` as a recipient.
+	///
+	/// Indices that are already recipients or aren't valid player indices are ignored.
+	pub fn add_recipient(&mut self, index: c_int) {
+		if !(1..=ABSOLUTE_PLAYER_LIMIT as c_int).contains(&index) || self.contains(index) {
+			return
+		}
+		let data = &mut self.object.data;
+		data.players[data.count] = index;
+		data.count += 1;
+	}
+
+	/// Adds the players with the given entity indices as recipients.
+	///
+	/// See also [`add_recipient`](Self::add_recipient).
+	pub fn add_recipients(&mut self, indices: impl IntoIterator<Item = c_int>) {
+		for index in indices {
+			self.add_recipient(index)
+		}
+	}
+
+	/// Adds the players whose bits are set in `bits` as recipients.
+	pub fn add_recipients_by_bits(&mut self, bits: &PlayerBitVec) {
+		self.add_recipients(bits.iter_ones().map(move |bit| bit as c_int + 1))
+	}
+
+	/// Removes the player with the entity index `index` from the recipients.
+	pub fn remove_recipient(&mut self, index: c_int) {
+		let data = &mut self.object.data;
+		if let Some(slot) = data.players[..data.count].iter().position(move |&i| i == index) {
+			data.players.copy_within(slot + 1..data.count, slot);
+			data.count -= 1;
+		}
+	}
+
+	/// Removes all recipients.
+	pub const fn remove_all_recipients(&mut self) {
+		self.object.data.count = 0;
+	}
+
+	/// Returns a pointer to the C++ object, which can be passed to the engine.
+	pub fn as_mut_ptr(&mut self) -> VtObjectMut<RecipientFilterVt> {
+		NonNull::from(self.object.as_mut_object())
+	}
+
+	const VTABLE: &RecipientFilterVt = &new_vtable_self!(RecipientFilterVt {
+		destructor,
+		#[cfg(not(windows))]
+		destructor_2,
+		is_reliable,
+		is_init_message,
+		get_recipient_count,
+		get_recipient_index
+	});
+
+	::rse_cpp::vtable_methods! {
+		this: VtObjectPtr<RecipientFilterVt>;
+		// The engine never destroys filters that are passed to it.
+		fn destructor() {}
+		#[cfg(not(windows))]
+		fn destructor_2() {}
+		fn is_reliable() -> bool {
+			this_to_self!(ref this).object.data.reliable
+		}
+		fn is_init_message() -> bool {
+			this_to_self!(ref this).object.data.init_message
+		}
+		fn get_recipient_count() -> c_int {
+			this_to_self!(ref this).object.data.count as _
+		}
+		fn get_recipient_index(slot: c_int) -> c_int {
+			let this = this_to_self!(ref this);
+			this.recipients().get(slot as usize).copied().unwrap_or(-1)
+		}
+	}
+}
+
+impl AsObject<RecipientFilterVt> for RecipientFilter {
+	fn as_object(&self) -> &VtObject<RecipientFilterVt> {
+		self.object.as_object()
+	}
+}
+
@@ -1,4 +1,11 @@
-use ::core::ffi::CStr;
+use ::core::{
+	ffi::{
+		CStr, c_int,
+	},
+	ptr::{
+		NonNull, null,
+	},
+};
 use ::rse_cpp::{
 	AsObject, virtual_call, owned_vt_object_wrapper,
 };
@@ -30,10 +37,95 @@ pub trait ServerGameDllImpl: AsObject<ServerGameDllVt> {
 		unsafe { CStr::from_ptr(virtual_call!(self.as_object() => get_game_description())) }
 	}
 
-	// TODO: More functions from `ServerGameDllVt`.
+	/// Returns `true` if a saved game is being restored.
+	fn is_restoring(&self) -> bool {
+		unsafe { virtual_call!(self.as_object() => is_restoring()) }
+	}
+	/// Returns `true` if the game DLL doesn't want the server to be made public.
+	fn should_hide_server(&self) -> bool {
+		unsafe { virtual_call!(self.as_object() => should_hide_server()) }
+	}
+	/// Returns the string shown in the "map" column of the server browser,
+	/// or `None` if the map name is shown.
+	fn server_browser_map_override(&self) -> Option<&CStr> {
+		unsafe {
+			let ptr = virtual_call!(self.as_object() => get_server_browser_map_override());
+			(!ptr.is_null()).then(move || CStr::from_ptr(ptr))
+		}
+	}
+	/// Returns the game data string sent to the master server.
+	fn server_browser_game_data(&self) -> Option<&CStr> {
+		unsafe {
+			let ptr = virtual_call!(self.as_object() => get_server_browser_game_data());
+			(!ptr.is_null()).then(move || CStr::from_ptr(ptr))
+		}
+	}
+	/// Returns `Ok(())` if the game DLL allows changing the map with `changelevel` or `map`,
+	/// or `Err` with the reason if it doesn't.
+	fn is_manual_map_change_okay(&self) -> Result<(), Option<&CStr>> {
+		unsafe {
+			let mut reason = null();
+			if virtual_call!(self.as_object() => is_manual_map_change_okay(&mut reason)) {
+				Ok(())
+			} else {
+				Err((!reason.is_null()).then(move || CStr::from_ptr(reason)))
+			}
+		}
+	}
+
+	/// Returns information about the user message of type `msg_type`,
+	/// writing its name to `name_buffer`,
+	/// or `None` if there is no such message.
+	/// 
+	/// User message types are numbered starting from `0`.
+	fn user_message_info<'a>(&self, msg_type: c_int, name_buffer: &'a mut [u8]) -> Option<UserMessageInfo<'a>> {
+		if name_buffer.is_empty() {
+			return None
+		}
+		let mut size = 0;
+		let found = unsafe {
+			virtual_call!(self.as_object() => get_user_message_info(
+				msg_type,
+				name_buffer.as_mut_ptr() as _, name_buffer.len().min(c_int::MAX as usize) as _,
+				NonNull::from(&mut size),
+			))
+		};
+		if !found {
+			return None
+		}
+		let name = CStr::from_bytes_until_nul(name_buffer).ok()?;
+		Some(UserMessageInfo {
+			name,
+			size: (size >= 0).then_some(size as _),
+		})
+	}
+	/// Returns the type of the user message named `name`,
+	/// compared case-insensitively like the game DLL does.
+	/// 
+	/// This function iterates over all user messages;
+	/// consider caching the result.
+	fn find_user_message(&self, name: &CStr) -> Option<c_int> {
+		let mut buffer = [0; MAX_USER_MESSAGE_NAME_LENGTH];
+		(0..).map_while(move |msg_type| {
+			let info = self.user_message_info(msg_type, &mut buffer)?;
+			Some(info.name.to_bytes().eq_ignore_ascii_case(name.to_bytes()).then_some(msg_type))
+		}).flatten().next()
+	}
 }
 impl<T: ?Sized + AsObject<ServerGameDllVt>> ServerGameDllImpl for T {}
 
+/// Maximum length of user message names, including the nul terminator.
+pub const MAX_USER_MESSAGE_NAME_LENGTH: usize = 256;
+
+/// Information about a user message, as returned by
+/// [`ServerGameDllImpl::user_message_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserMessageInfo<'a> {
+	pub name: &'a CStr,
+	/// Size of the message in bytes, or `None` if the message has a variable size.
+	pub size: Option<usize>,
+}
+
 owned_vt_object_wrapper! {
 	pub struct ServerGameDll for ServerGameDllVt;
 }
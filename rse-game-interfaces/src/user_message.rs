@@ -0,0 +1,49 @@
+use ::core::ops::{
+	Deref, DerefMut,
+};
+use ::rse_cpp::{
+	VtObject, virtual_call,
+};
+use ::rse_shared::bitbuf::BitWriter;
+
+use crate::cppdef::VEngineServerVt;
+
+/// User message that is being written,
+/// which is sent to its recipients when dropped.
+/// 
+/// See [`VEngineServerImpl::user_message`](crate::VEngineServerImpl::user_message).
+pub struct UserMessage<'a> {
+	engine: &'a VtObject<VEngineServerVt>,
+	writer: &'a mut BitWriter<'a>,
+}
+
+impl<'a> UserMessage<'a> {
+	/// # Safety
+	/// `writer` must be the buffer returned by `IVEngineServer::UserMessageBegin` on `engine`,
+	/// and the message must not have been ended yet.
+	pub(crate) const unsafe fn new(engine: &'a VtObject<VEngineServerVt>, writer: &'a mut BitWriter<'a>) -> Self {
+		Self {
+			engine,
+			writer,
+		}
+	}
+}
+
+impl<'a> Deref for UserMessage<'a> {
+	type Target = BitWriter<'a>;
+	fn deref(&self) -> &Self::Target {
+		self.writer
+	}
+}
+
+impl DerefMut for UserMessage<'_> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.writer
+	}
+}
+
+impl Drop for UserMessage<'_> {
+	fn drop(&mut self) {
+		unsafe { virtual_call!(self.engine => message_end()) }
+	}
+}
@@ -0,0 +1,420 @@
+//! Typed versions of the standard user messages, encoded like the game DLLs encode them.
+//!
+//! The type of a user message differs between mods,
+//! so it must be looked up with its [`NAME`](UserMessageData::NAME)
+//! (see [`ServerGameDllImpl::find_user_message`](crate::ServerGameDllImpl::find_user_message)).
+//! Not every mod implements every message.
+
+use ::core::ffi::{
+	CStr, c_int,
+};
+use ::rse_math::Color;
+use ::rse_shared::bitbuf::BitWriter;
+
+/// Trait for data of user messages that can be sent to clients.
+pub trait UserMessageData {
+	/// Name of the user message.
+	const NAME: &'static CStr;
+	/// Writes the fields of the user message to `writer`.
+	fn write(&self, writer: &mut BitWriter<'_>);
+}
+
+/// Chat message that is printed as-is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SayText<'a> {
+	/// Entity index of the player that sent the message, or `0` for the server.
+	pub client: u8,
+	pub text: &'a CStr,
+	/// `true` if the message should be filtered by the chat filter of the client.
+	pub chat: bool,
+}
+
+impl UserMessageData for SayText<'_> {
+	const NAME: &'static CStr = c"SayText";
+	fn write(&self, writer: &mut BitWriter<'_>) {
+		writer.write_byte(self.client);
+		writer.write_string(self.text);
+		writer.write_byte(self.chat as _);
+	}
+}
+
+/// Chat message that is localized on the client, with up to four parameters.
+///
+/// In most mods, the player name of the [`client`](Self::client) is used to color the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SayText2<'a> {
+	/// Entity index of the player that sent the message, or `0` for the server.
+	pub client: u8,
+	/// `true` if the message should be filtered by the chat filter of the client.
+	pub chat: bool,
+	/// Localization token or text of the message.
+	pub message: &'a CStr,
+	pub params: [&'a CStr; 4],
+}
+
+impl<'a> SayText2<'a> {
+	/// Returns a new message with no parameters.
+	pub const fn new(client: u8, chat: bool, message: &'a CStr) -> Self {
+		Self {
+			client,
+			chat,
+			message,
+			params: [c""; 4],
+		}
+	}
+}
+
+impl UserMessageData for SayText2<'_> {
+	const NAME: &'static CStr = c"SayText2";
+	fn write(&self, writer: &mut BitWriter<'_>) {
+		writer.write_byte(self.client);
+		writer.write_byte(self.chat as _);
+		writer.write_string(self.message);
+		for param in self.params {
+			writer.write_string(param);
+		}
+	}
+}
+
+/// Destination of a [`TextMsg`] (`HUD_PRINT*`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum HudPrint {
+	Notify = 1,
+	Console = 2,
+	Talk = 3,
+	Center = 4,
+}
+
+/// Message that is localized on the client, with up to four parameters,
+/// and printed to the given destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextMsg<'a> {
+	pub dest: HudPrint,
+	/// Localization token or text of the message.
+	pub message: &'a CStr,
+	pub params: [&'a CStr; 4],
+}
+
+impl<'a> TextMsg<'a> {
+	/// Returns a new message with no parameters.
+	pub const fn new(dest: HudPrint, message: &'a CStr) -> Self {
+		Self {
+			dest,
+			message,
+			params: [c""; 4],
+		}
+	}
+}
+
+impl UserMessageData for TextMsg<'_> {
+	const NAME: &'static CStr = c"TextMsg";
+	fn write(&self, writer: &mut BitWriter<'_>) {
+		writer.write_byte(self.dest as _);
+		writer.write_string(self.message);
+		for param in self.params {
+			writer.write_string(param);
+		}
+	}
+}
+
+/// Hint that is shown in a box on the HUD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HintText<'a> {
+	pub text: &'a CStr,
+}
+
+impl UserMessageData for HintText<'_> {
+	const NAME: &'static CStr = c"HintText";
+	fn write(&self, writer: &mut BitWriter<'_>) {
+		writer.write_string(self.text);
+	}
+}
+
+/// Hints that are shown in the key hint panel of the HUD.
+///
+/// At most 255 hints are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyHintText<'a> {
+	pub hints: &'a [&'a CStr],
+}
+
+impl UserMessageData for KeyHintText<'_> {
+	const NAME: &'static CStr = c"KeyHintText";
+	fn write(&self, writer: &mut BitWriter<'_>) {
+		let hints = &self.hints[..self.hints.len().min(u8::MAX as usize)];
+		writer.write_byte(hints.len() as _);
+		for hint in hints {
+			writer.write_string(hint);
+		}
+	}
+}
+
+/// Maximum number of [`HudMsg`] channels.
+pub const MAX_HUD_MSG_CHANNELS: u8 = 6;
+
+/// Effect used to show a [`HudMsg`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum HudMsgEffect {
+	/// Fade in and out.
+	#[default]
+	Fade = 0,
+	/// Flicker with the second color.
+	Flicker = 1,
+	/// Write out the text one character at a time,
+	/// highlighting each character with the second color.
+	WriteOut = 2,
+}
+
+/// Text shown on the HUD at an arbitrary position (`game_text`).
+///
+/// Not every mod shows these messages.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HudMsg<'a> {
+	/// Channel of the message, which replaces previous messages on the same channel.
+	///
+	/// Only the channels below [`MAX_HUD_MSG_CHANNELS`] are used.
+	pub channel: u8,
+	/// Horizontal position of the message from `0.0` to `1.0`, or `-1.0` to center it.
+	pub x: f32,
+	/// Vertical position of the message from `0.0` to `1.0`, or `-1.0` to center it.
+	pub y: f32,
+	pub color: Color,
+	/// Color used by the [`effect`](Self::effect).
+	pub effect_color: Color,
+	pub effect: HudMsgEffect,
+	/// Time to fade in, in seconds.
+	pub fade_in: f32,
+	/// Time to fade out, in seconds.
+	pub fade_out: f32,
+	/// Time to show the message, in seconds.
+	pub hold: f32,
+	/// Time used by the [`effect`](Self::effect), in seconds.
+	pub fx_time: f32,
+	pub text: &'a CStr,
+}
+
+impl UserMessageData for HudMsg<'_> {
+	const NAME: &'static CStr = c"HudMsg";
+	fn write(&self, writer: &mut BitWriter<'_>) {
+		writer.write_byte(self.channel);
+		writer.write_float(self.x);
+		writer.write_float(self.y);
+		writer.write_bytes(&self.color.rgba);
+		writer.write_bytes(&self.effect_color.rgba);
+		writer.write_byte(self.effect as _);
+		writer.write_float(self.fade_in);
+		writer.write_float(self.fade_out);
+		writer.write_float(self.hold);
+		writer.write_float(self.fx_time);
+		writer.write_string(self.text);
+	}
+}
+
+::rse_cpp::bitflags! {
+	/// Flags of a [`Fade`] (`FFADE_*`).
+	#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	#[repr(transparent)]
+	pub struct FadeFlags: u16 {
+		/// Fade in from the color, rather than out to it.
+		const IN = 0x0001;
+		/// Fade out to the color.
+		const OUT = 0x0002;
+		/// Modulate the screen with the color, rather than blending it.
+		const MODULATE = 0x0004;
+		/// Stay faded out until another fade is sent.
+		const STAY_OUT = 0x0008;
+		/// Replace any fades that are in progress.
+		const PURGE = 0x0010;
+	}
+}
+
+/// Number of fractional bits used for the times of a [`Fade`].
+pub const SCREENFADE_FRACBITS: u32 = 9;
+
+/// Fade of the screen to or from a color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fade {
+	/// Duration of the fade, in seconds.
+	pub duration: f32,
+	/// Time to hold the color after the fade, in seconds.
+	pub hold: f32,
+	pub flags: FadeFlags,
+	pub color: Color,
+}
+
+impl Fade {
+	/// Returns `seconds` as a fixed-point number with [`SCREENFADE_FRACBITS`] fractional bits,
+	/// like `FixedUnsigned16`.
+	const fn fixed_time(seconds: f32) -> u16 {
+		let fixed = (seconds * (1 << SCREENFADE_FRACBITS) as f32) as c_int;
+		if fixed < 0 {
+			0
+		} else if fixed > u16::MAX as c_int {
+			u16::MAX
+		} else {
+			fixed as _
+		}
+	}
+}
+
+impl UserMessageData for Fade {
+	const NAME: &'static CStr = c"Fade";
+	fn write(&self, writer: &mut BitWriter<'_>) {
+		writer.write_word(Self::fixed_time(self.duration));
+		writer.write_word(Self::fixed_time(self.hold));
+		writer.write_word(self.flags.bits());
+		writer.write_bytes(&self.color.rgba);
+	}
+}
+
+/// Command of a [`Shake`] (`ShakeCommand_t`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum ShakeCommand {
+	/// Start a shake.
+	#[default]
+	Start = 0,
+	/// Stop all shakes.
+	Stop,
+	/// Change the amplitude of the current shake.
+	Amplitude,
+	/// Change the frequency of the current shake.
+	Frequency,
+	/// Start a shake that only rumbles controllers.
+	StartRumbleOnly,
+	/// Start a shake that doesn't rumble controllers.
+	StartNoRumble,
+}
+
+/// Shake of the screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shake {
+	pub command: ShakeCommand,
+	pub amplitude: f32,
+	pub frequency: f32,
+	/// Duration of the shake, in seconds.
+	pub duration: f32,
+}
+
+impl UserMessageData for Shake {
+	const NAME: &'static CStr = c"Shake";
+	fn write(&self, writer: &mut BitWriter<'_>) {
+		writer.write_byte(self.command as _);
+		writer.write_float(self.amplitude);
+		writer.write_float(self.frequency);
+		writer.write_float(self.duration);
+	}
+}
+
+/// Message that shows or hides a viewport panel, like `info` for MOTDs.
+///
+/// At most 255 key-value pairs are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VguiMenu<'a> {
+	/// Name of the panel.
+	pub name: &'a CStr,
+	pub show: bool,
+	/// Key-value pairs passed to the panel.
+	pub data: &'a [(&'a CStr, &'a CStr)],
+}
+
+impl UserMessageData for VguiMenu<'_> {
+	const NAME: &'static CStr = c"VGUIMenu";
+	fn write(&self, writer: &mut BitWriter<'_>) {
+		writer.write_string(self.name);
+		writer.write_byte(self.show as _);
+		let data = &self.data[..self.data.len().min(u8::MAX as usize)];
+		writer.write_byte(data.len() as _);
+		for &(key, value) in data {
+			writer.write_string(key);
+			writer.write_string(value);
+		}
+	}
+}
+
+/// Message that resets the HUD of a player.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ResetHud;
+
+impl UserMessageData for ResetHud {
+	const NAME: &'static CStr = c"ResetHUD";
+	fn write(&self, writer: &mut BitWriter<'_>) {
+		writer.write_byte(0);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ::rse_shared::bitbuf::BitBuffer;
+
+	use super::*;
+
+	fn encode(message: &impl UserMessageData) -> ([u8; 256], usize) {
+		let mut buffer = BitBuffer::<64>::new();
+		let mut writer = buffer.writer();
+		message.write(&mut writer);
+		assert!(!writer.is_overflowed());
+		let len = writer.bytes_written();
+		let mut bytes = [0; 256];
+		bytes[..len].copy_from_slice(&buffer.as_bytes()[..len]);
+		(bytes, len)
+	}
+
+	macro_rules! assert_encodes {
+		($message:expr, $expected:expr) => {{
+			let (bytes, len) = encode(&$message);
+			assert_eq!(&bytes[..len], &$expected[..]);
+		}};
+	}
+
+	#[test]
+	fn encodings() {
+		assert_encodes!(SayText { client: 2, text: c"hi", chat: true }, b"\x02hi\0\x01");
+		assert_encodes!(
+			SayText2 {
+				params: [c"Bob", c"", c"", c""],
+				..SayText2::new(1, true, c"#Chat")
+			},
+			b"\x01\x01#Chat\0Bob\0\0\0\0"
+		);
+		assert_encodes!(TextMsg::new(HudPrint::Center, c"Go"), b"\x04Go\0\0\0\0\0");
+		assert_encodes!(KeyHintText { hints: &[c"a", c"b"] }, b"\x02a\0b\0");
+		assert_encodes!(
+			HudMsg {
+				channel: 1,
+				x: -1.0,
+				y: 0.5,
+				color: Color::rgba(255, 0, 0, 255),
+				effect_color: Color::rgba(0, 0, 0, 0),
+				effect: HudMsgEffect::WriteOut,
+				fade_in: 0.0,
+				fade_out: 1.0,
+				hold: 2.0,
+				fx_time: 0.25,
+				text: c"x",
+			},
+			b"\x01\x00\x00\x80\xBF\x00\x00\x00\x3F\xFF\x00\x00\xFF\x00\x00\x00\x00\x02\
+			\x00\x00\x00\x00\x00\x00\x80\x3F\x00\x00\x00\x40\x00\x00\x80\x3Ex\0"
+		);
+		assert_encodes!(
+			Fade {
+				duration: 1.5,
+				hold: 200.0,
+				flags: FadeFlags::IN | FadeFlags::PURGE,
+				color: Color::rgba(255, 0, 0, 128),
+			},
+			b"\x00\x03\xFF\xFF\x11\x00\xFF\x00\x00\x80"
+		);
+		assert_encodes!(
+			Shake { command: ShakeCommand::Start, amplitude: 4.0, frequency: 1.0, duration: 0.5 },
+			b"\x00\x00\x00\x80\x40\x00\x00\x80\x3F\x00\x00\x00\x3F"
+		);
+		assert_encodes!(
+			VguiMenu { name: c"info", show: true, data: &[(c"title", c"MOTD")] },
+			b"info\0\x01\x01title\0MOTD\0"
+		);
+		assert_encodes!(ResetHud, b"\x00");
+	}
+}
@@ -11,6 +11,10 @@ use ::rse_shared::cppdef::{
 	KeyValues,
 };
 
+pub use ::rse_game_interfaces::cppdef::{
+	QueryCvarCookie, QueryCvarValueStatus,
+};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(C)]
 pub enum PluginResult {
@@ -22,35 +26,6 @@ pub enum PluginResult {
 	Stop,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[repr(C)]
-pub enum QueryCvarValueStatus {
-	/// Got the value fine.
-	ValueIntact = 0,
-	/// There is no ConVar or ConCommand with the given name.
-	CvarNotFound = 1,
-	/// There's a ConCommand, but it's not a ConVar.
-	NotACvar = 2,
-	/// The cvar was marked with `FCVAR_SERVER_CAN_NOT_QUERY`,
-	/// so the server is not allowed to have its value.
-	CvarProtected = 3,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[repr(transparent)]
-pub struct QueryCvarCookie(pub c_int);
-impl QueryCvarCookie {
-	pub const INVALID: Self = Self(-1);
-
-	pub const fn is_valid(&self) -> bool {
-		self.0 != Self::INVALID.0
-	}
-
-	pub const fn is_invalid(&self) -> bool {
-		self.0 == Self::INVALID.0
-	}
-}
-
 pub const INTERFACEVERSION_ISERVERPLUGINCALLBACKS: &CStr = c"ISERVERPLUGINCALLBACKS003";
 
 pub type ClientIndex = c_int;
@@ -107,6 +82,8 @@ pub enum DialogType {
 	AskConnect,
 }
 
+pub const INTERFACEVERSION_ISERVERPLUGINHELPERS: &CStr = c"ISERVERPLUGINHELPERS001";
+
 vtable! {
 	pub ServerPluginHelpersVt {
 		pub fn create_message(
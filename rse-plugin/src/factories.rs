@@ -1,13 +1,17 @@
+use ::rse_cpp::VtObjectMut;
 use ::rse_game_interfaces::InterfaceOfFactory;
 use ::rse_interface::{
 	CreateInterfaceFn, RawInterfaceFactory,
 	InterfaceFactory,
 };
 
+use crate::cppdef::ServerPluginCallbacksVt;
+
 #[derive(Debug, Clone, Copy)]
 pub struct PluginFactories {
 	app_system_factory: CreateInterfaceFn,
 	game_server_factory: CreateInterfaceFn,
+	callbacks: Option<VtObjectMut<ServerPluginCallbacksVt>>,
 }
 
 impl PluginFactories {
@@ -17,9 +21,16 @@ impl PluginFactories {
 		Self {
 			app_system_factory,
 			game_server_factory,
+			callbacks: None,
 		}
 	}
 
+	/// Returns `self` with `callbacks` as the C++ object of the plugin that is being loaded.
+	pub const fn with_callbacks(mut self, callbacks: VtObjectMut<ServerPluginCallbacksVt>) -> Self {
+		self.callbacks = Some(callbacks);
+		self
+	}
+
 	pub fn create_interface<I>(&self) -> Result<I, I::Error>
 	where
 		I: InterfaceOfFactory,
@@ -36,6 +47,14 @@ impl PluginFactories {
 	pub const fn game_server_factory(&self) -> CreateInterfaceFn {
 		self.game_server_factory
 	}
+
+	/// Returns the C++ object of the plugin that is being loaded, if known.
+	/// 
+	/// This is the object that identifies the plugin to
+	/// [`ServerPluginHelpersImpl::create_message`](crate::ServerPluginHelpersImpl::create_message).
+	pub const fn callbacks(&self) -> Option<VtObjectMut<ServerPluginCallbacksVt>> {
+		self.callbacks
+	}
 }
 
 pub trait Factory: RawInterfaceFactory {
@@ -31,6 +31,8 @@ mod loadable_plugin;
 pub use loadable_plugin::*;
 mod reject_reason;
 pub use reject_reason::*;
+mod plugin_helpers;
+pub use plugin_helpers::*;
 
 pub mod prelude {
 	pub use ::core::ffi::CStr;
@@ -0,0 +1,61 @@
+use ::core::ffi::CStr;
+use ::rse_cpp::{
+	AsObject, VtObjectMut, virtual_call, owned_vt_object_wrapper,
+};
+use ::rse_game_interfaces::{
+	InterfaceOfFactory, AppSystemFactory,
+};
+use ::rse_shared::{
+	keyvalues::EngineKeyValues,
+	ServerEdict,
+};
+
+use crate::cppdef::{
+	DialogType, QueryCvarCookie,
+	ServerPluginCallbacksVt, ServerPluginHelpersVt,
+	INTERFACEVERSION_ISERVERPLUGINHELPERS,
+};
+
+/// Safe interface to `IServerPluginHelpers`.
+pub trait ServerPluginHelpersImpl: AsObject<ServerPluginHelpersVt> {
+	/// Shows a dialog of type `dialog_type` with the contents of `data` to the client of `entity`.
+	///
+	/// `plugin` must be the C++ object of a loaded plugin
+	/// (see [`PluginFactories::callbacks`](crate::PluginFactories::callbacks)),
+	/// or else the engine will not show the dialog.
+	fn create_message(
+		&self,
+		entity: &ServerEdict, dialog_type: DialogType, data: &mut EngineKeyValues<'_>,
+		plugin: VtObjectMut<ServerPluginCallbacksVt>,
+	) {
+		unsafe { virtual_call!(self.as_object() => create_message(
+			entity.as_ptr() as _, dialog_type, data.as_mut_ptr(), plugin,
+		)) }
+	}
+	/// Makes the client of `entity` execute `command` as if it was entered into its console.
+	fn client_command(&self, entity: &ServerEdict, command: &CStr) {
+		unsafe { virtual_call!(self.as_object() => client_command(entity.as_ptr() as _, command.as_ptr())) }
+	}
+	/// Starts querying the value of the ConVar named `name` of the client of `entity`,
+	/// returning `None` if the query couldn't be started.
+	///
+	/// The result is passed to [`Plugin::on_query_cvar_value_finished`](crate::Plugin::on_query_cvar_value_finished)
+	/// with the returned cookie.
+	fn start_query_cvar_value(&self, entity: &ServerEdict, name: &CStr) -> Option<QueryCvarCookie> {
+		let cookie = unsafe {
+			virtual_call!(self.as_object() => start_query_cvar_value(entity.as_ptr() as _, name.as_ptr()))
+		};
+		cookie.is_valid().then_some(cookie)
+	}
+}
+impl<T: ?Sized + AsObject<ServerPluginHelpersVt>> ServerPluginHelpersImpl for T {}
+
+owned_vt_object_wrapper! {
+	pub struct ServerPluginHelpers for ServerPluginHelpersVt;
+}
+unsafe impl ::rse_interface::Interface for ServerPluginHelpers {
+	const IDENTIFIER: &CStr = INTERFACEVERSION_ISERVERPLUGINHELPERS;
+}
+impl InterfaceOfFactory for ServerPluginHelpers {
+	type Factory = AppSystemFactory;
+}
@@ -132,7 +132,7 @@ where
 	::rse_cpp::vtable_methods! {
 		this: VtObjectPtr<ServerPluginCallbacksVt>;
 		fn load(interface_factory: CreateInterfaceFn, game_server_factory: CreateInterfaceFn) -> bool {
-			let factories = PluginFactories::new(interface_factory, game_server_factory);
+			let factories = PluginFactories::new(interface_factory, game_server_factory).with_callbacks(this);
 			unsafe { this_to_self!(mut this).object.data.load(factories) }
 		}
 		fn unload() {
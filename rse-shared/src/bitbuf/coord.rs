@@ -0,0 +1,46 @@
+//! Constants for the quantized encodings used by the engine's bit buffers.
+//!
+//! Cross-referenced with `coordsize.h` and `bitbuf.h` in `tier1`.
+
+use ::core::ffi::c_float;
+
+/// Number of bits used for the integral part of a bit-coord.
+pub const COORD_INTEGER_BITS: u32 = 14;
+/// Number of bits used for the fractional part of a bit-coord.
+pub const COORD_FRACTIONAL_BITS: u32 = 5;
+pub const COORD_DENOMINATOR: u32 = 1 << COORD_FRACTIONAL_BITS;
+/// Smallest difference between two representable bit-coords.
+pub const COORD_RESOLUTION: c_float = 1.0 / COORD_DENOMINATOR as c_float;
+
+/// Number of bits used for the fractional part of a bit-normal.
+pub const NORMAL_FRACTIONAL_BITS: u32 = 11;
+pub const NORMAL_DENOMINATOR: u32 = (1 << NORMAL_FRACTIONAL_BITS) - 1;
+/// Smallest difference between two representable bit-normals.
+pub const NORMAL_RESOLUTION: c_float = 1.0 / NORMAL_DENOMINATOR as c_float;
+
+/// Maximum number of bytes that a 32-bit var-int can take up.
+pub const MAX_VAR_INT32_BYTES: u32 = 5;
+/// Maximum number of bytes that a 64-bit var-int can take up.
+pub const MAX_VAR_INT64_BYTES: u32 = 10;
+
+/// Returns `value` with its sign moved into the least-significant bit,
+/// so that small negative numbers stay small when encoded as a var-int.
+pub const fn zig_zag_encode32(value: i32) -> u32 {
+	((value << 1) ^ (value >> 31)) as u32
+}
+
+/// Reverses [`zig_zag_encode32`].
+pub const fn zig_zag_decode32(value: u32) -> i32 {
+	((value >> 1) as i32) ^ -((value & 1) as i32)
+}
+
+/// Returns `value` with its sign moved into the least-significant bit,
+/// so that small negative numbers stay small when encoded as a var-int.
+pub const fn zig_zag_encode64(value: i64) -> u64 {
+	((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Reverses [`zig_zag_encode64`].
+pub const fn zig_zag_decode64(value: u64) -> i64 {
+	((value >> 1) as i64) ^ -((value & 1) as i64)
+}
@@ -0,0 +1,169 @@
+//! Safe bit buffers compatible with the engine's `bf_read` and `bf_write`.
+
+use ::core::slice::from_raw_parts;
+
+mod coord;
+pub use coord::*;
+mod reader;
+pub use reader::*;
+mod writer;
+pub use writer::*;
+
+/// Owned, zero-initialized storage for a [`BitWriter`] of `WORDS` 32-bit words.
+///
+/// This is useful for building messages without an engine-owned buffer,
+/// and for reading back what was written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitBuffer<const WORDS: usize> {
+	words: [u32; WORDS],
+}
+
+impl<const WORDS: usize> Default for BitBuffer<WORDS> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<const WORDS: usize> BitBuffer<WORDS> {
+	/// Size of the buffer in bytes.
+	pub const BYTES: usize = WORDS * 4;
+
+	pub const fn new() -> Self {
+		Self {
+			words: [0; WORDS],
+		}
+	}
+
+	/// Returns a new writer that starts writing at the beginning of the buffer.
+	pub fn writer(&mut self) -> BitWriter<'_> {
+		BitWriter::new(&mut self.words)
+	}
+
+	/// Returns a new reader over the first `n_bits` bits of the buffer.
+	///
+	/// # Panics
+	/// The function will panic if `n_bits` is greater than the size of the buffer in bits.
+	pub fn reader(&self, n_bits: usize) -> BitReader<'_> {
+		BitReader::with_bits(self.as_bytes(), n_bits)
+	}
+
+	/// Returns the contents of the buffer as bytes, in the order that the engine would send them.
+	pub const fn as_bytes(&self) -> &[u8] {
+		// Words are always stored in little-endian order, see `BitWriter`.
+		unsafe { from_raw_parts(self.words.as_ptr() as *const u8, Self::BYTES) }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ::rse_math::Vector;
+
+	#[test]
+	fn ubits_across_words() {
+		let mut buffer = BitBuffer::<4>::new();
+		let mut w = buffer.writer();
+		w.write_ubit_long(0b101, 3);
+		w.write_ubit_long(0xdeadbeef, 32);
+		w.write_sbit_long(-3, 5);
+		w.write_ubit_long_long(0x0123_4567_89ab_cdef, 64);
+		let n_bits = w.bits_written();
+		assert_eq!(n_bits, 3 + 32 + 5 + 64);
+		assert!(!w.is_overflowed());
+
+		let mut r = buffer.reader(n_bits);
+		assert_eq!(r.read_ubit_long(3), 0b101);
+		assert_eq!(r.read_ubit_long(32), 0xdeadbeef);
+		assert_eq!(r.read_sbit_long(5), -3);
+		assert_eq!(r.read_ubit_long_long(64), 0x0123_4567_89ab_cdef);
+		assert_eq!(r.bits_left(), 0);
+		assert!(!r.is_overflowed());
+	}
+
+	#[test]
+	fn byte_layout() {
+		let mut buffer = BitBuffer::<2>::new();
+		let mut w = buffer.writer();
+		w.write_one_bit(true);
+		w.write_byte(0xff);
+		w.write_word(0x1234);
+		assert_eq!(w.data(), &[0xff, 0x69, 0x24, 0x00]);
+		assert_eq!(w.bytes_written(), 4);
+	}
+
+	#[test]
+	fn var_ints() {
+		let mut buffer = BitBuffer::<8>::new();
+		let mut w = buffer.writer();
+		w.write_var_int32(300);
+		w.write_signed_var_int32(-1);
+		w.write_var_int64(u64::MAX);
+		w.write_signed_var_int64(i64::MIN);
+		assert_eq!(&w.data()[..3], &[0xac, 0x02, 0x01]);
+		let n_bits = w.bits_written();
+
+		let mut r = buffer.reader(n_bits);
+		assert_eq!(r.read_var_int32(), 300);
+		assert_eq!(r.read_signed_var_int32(), -1);
+		assert_eq!(r.read_var_int64(), u64::MAX);
+		assert_eq!(r.read_signed_var_int64(), i64::MIN);
+		assert!(!r.is_overflowed());
+	}
+
+	#[test]
+	fn strings() {
+		let mut buffer = BitBuffer::<4>::new();
+		let mut w = buffer.writer();
+		w.write_one_bit(false);
+		w.write_string(c"hello");
+		w.write_string(c"world!");
+		let n_bits = w.bits_written();
+
+		let mut r = buffer.reader(n_bits);
+		assert!(!r.read_one_bit());
+		let mut out = [0; 16];
+		assert_eq!(r.read_string(&mut out), Some(c"hello"));
+		let mut small = [0; 4];
+		assert_eq!(r.read_string(&mut small), None);
+		assert_eq!(r.bits_left(), 0);
+		assert!(!r.is_overflowed());
+	}
+
+	#[test]
+	fn quantized() {
+		let mut buffer = BitBuffer::<8>::new();
+		let mut w = buffer.writer();
+		w.write_bit_coord(-1234.5);
+		w.write_bit_coord(0.0);
+		w.write_bit_normal(-0.5);
+		w.write_bit_angle(90.0, 8);
+		w.write_bit_vec3_coord(&Vector::new(16.0, 0.0, -0.25));
+		let n_bits = w.bits_written();
+
+		let mut r = buffer.reader(n_bits);
+		assert_eq!(r.read_bit_coord(), -1234.5);
+		assert_eq!(r.read_bit_coord(), 0.0);
+		assert!((r.read_bit_normal() + 0.5).abs() <= NORMAL_RESOLUTION);
+		assert_eq!(r.read_bit_angle(8), 90.0);
+		assert_eq!(r.read_bit_vec3_coord(), Vector::new(16.0, 0.0, -0.25));
+		assert_eq!(r.bits_left(), 0);
+	}
+
+	#[test]
+	fn overflow() {
+		let mut buffer = BitBuffer::<1>::new();
+		let mut w = buffer.writer();
+		w.write_ubit_long(0, 30);
+		assert!(!w.is_overflowed());
+		w.write_byte(0xff);
+		assert!(w.is_overflowed());
+		assert_eq!(w.bits_left(), 0);
+
+		let mut r = buffer.reader(8);
+		assert_eq!(r.read_long(), 0);
+		assert!(r.is_overflowed());
+		r.reset();
+		assert_eq!(r.read_byte(), 0);
+		assert!(!r.is_overflowed());
+	}
+}
@@ -0,0 +1,409 @@
+use ::core::{
+	ffi::{
+		CStr, c_int,
+	},
+	marker::PhantomData,
+	ptr::null,
+	slice::from_raw_parts,
+};
+use ::rse_math::{
+	QAngle, Vector,
+};
+
+use crate::cppdef::BfRead;
+
+use super::{
+	COORD_INTEGER_BITS, COORD_FRACTIONAL_BITS, COORD_RESOLUTION,
+	MAX_VAR_INT32_BYTES, MAX_VAR_INT64_BYTES,
+	NORMAL_FRACTIONAL_BITS, NORMAL_RESOLUTION,
+	zig_zag_decode32, zig_zag_decode64,
+};
+
+/// Bit-level reader over a byte buffer,
+/// compatible with the engine's `bf_read`.
+///
+/// Reads never panic.
+/// Instead, reading past the end of the buffer sets the overflow flag,
+/// which can be checked with [`is_overflowed`](Self::is_overflowed),
+/// and the offending read returns zero.
+///
+/// # Layout
+/// This type has the exact same layout as a C++ [`bf_read`](BfRead).
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct BitReader<'a>(BfRead, PhantomData<&'a [u8]>);
+
+impl<'a> BitReader<'a> {
+	/// Returns a new reader over all bits of `data`.
+	///
+	/// # Panics
+	/// The function will panic if `data` is larger than what the engine can address in bits.
+	pub fn new(data: &'a [u8]) -> Self {
+		Self::with_bits(data, data.len() * 8)
+	}
+
+	/// Returns a new reader over the first `n_bits` bits of `data`.
+	///
+	/// # Panics
+	/// The function will panic if `data` is shorter than `n_bits`,
+	/// or if `data` is larger than what the engine can address in bits.
+	pub fn with_bits(data: &'a [u8], n_bits: usize) -> Self {
+		assert!(n_bits <= data.len() * 8, "`data` should contain at least `n_bits` bits");
+		let data_bytes = c_int::try_from(data.len()).ok()
+			.filter(move |&n| n <= c_int::MAX / 8)
+			.expect("buffer should be addressable in bits");
+		Self(BfRead {
+			data: data.as_ptr(),
+			data_bytes,
+			data_bits: n_bits as _,
+			current_bit: 0,
+			overflow: false,
+			assert_on_overflow: false,
+			debug_name: null(),
+		}, PhantomData)
+	}
+
+	/// Returns a mutable reference to a [`BitReader`] given a raw pointer to an engine-owned [`BfRead`].
+	///
+	/// # Safety
+	/// `ptr` must point to a valid, mutable [`bf_read`](BfRead)
+	/// whose buffer is valid for the lifetime `'a`.
+	pub const unsafe fn from_mut_ptr(ptr: *mut BfRead) -> &'a mut Self {
+		unsafe { &mut *(ptr as *mut Self) }
+	}
+
+	::rse_cpp::transparent_as_ptr_impls!(BitReader for BfRead as "bf_read");
+	::rse_cpp::transparent_inner_impls!(BitReader for BfRead as "bf_read");
+
+	/// Returns `true` if a read went past the end of the buffer.
+	pub const fn is_overflowed(&self) -> bool {
+		self.0.overflow
+	}
+
+	/// Returns the number of bits read so far.
+	pub const fn bits_read(&self) -> usize {
+		self.0.current_bit as _
+	}
+
+	/// Returns the number of bytes read so far, rounded up.
+	pub const fn bytes_read(&self) -> usize {
+		self.bits_read().div_ceil(8)
+	}
+
+	/// Returns the total number of bits that can be read from the buffer.
+	pub const fn max_bits(&self) -> usize {
+		self.0.data_bits as _
+	}
+
+	/// Returns the number of bits that can still be read.
+	pub const fn bits_left(&self) -> usize {
+		(self.0.data_bits - self.0.current_bit) as _
+	}
+
+	/// Returns the whole underlying buffer.
+	pub const fn data(&self) -> &'a [u8] {
+		unsafe { from_raw_parts(self.0.data, self.0.data_bytes as _) }
+	}
+
+	/// Moves the read position to the start of the buffer and clears the overflow flag.
+	pub const fn reset(&mut self) {
+		self.0.current_bit = 0;
+		self.0.overflow = false;
+	}
+
+	/// Moves the read position to `bit`.
+	///
+	/// Seeking past the end of the buffer sets the overflow flag.
+	pub const fn seek_to_bit(&mut self, bit: usize) {
+		if bit <= self.max_bits() {
+			self.0.current_bit = bit as _;
+		} else {
+			self.set_overflowed();
+		}
+	}
+
+	/// Skips `n_bits` bits.
+	pub const fn seek_relative(&mut self, n_bits: usize) {
+		if n_bits <= self.bits_left() {
+			self.0.current_bit += n_bits as c_int;
+		} else {
+			self.set_overflowed();
+		}
+	}
+
+	const fn set_overflowed(&mut self) {
+		self.0.current_bit = self.0.data_bits;
+		self.0.overflow = true;
+	}
+
+	/// Returns `true` if `n_bits` more bits can be read,
+	/// otherwise marks the buffer as overflowed.
+	const fn reserve(&mut self, n_bits: u32) -> bool {
+		if self.bits_left() >= n_bits as usize {
+			true
+		} else {
+			self.set_overflowed();
+			false
+		}
+	}
+
+	/// Reads `n_bits` bits at the current position, which must have been reserved.
+	const fn take_bits(&mut self, n_bits: u32) -> u32 {
+		let data = self.data();
+		let mut result = 0u32;
+		let mut taken = 0;
+		while taken < n_bits {
+			let bit = self.0.current_bit as usize;
+			let offset = (bit & 7) as u32;
+			let count = if 8 - offset < n_bits - taken { 8 - offset } else { n_bits - taken };
+			let chunk = (data[bit >> 3] as u32 >> offset) & ((1 << count) - 1);
+			result |= chunk << taken;
+			taken += count;
+			self.0.current_bit += count as c_int;
+		}
+		result
+	}
+
+	/// Reads a single bit.
+	pub const fn read_one_bit(&mut self) -> bool {
+		self.reserve(1) && self.take_bits(1) != 0
+	}
+
+	/// Reads an unsigned integer of `n_bits` bits.
+	///
+	/// # Panics
+	/// The function will panic if `n_bits` is greater than `32`.
+	pub const fn read_ubit_long(&mut self, n_bits: u32) -> u32 {
+		assert!(n_bits <= 32, "`n_bits` should be at most 32");
+		if self.reserve(n_bits) {
+			self.take_bits(n_bits)
+		} else {
+			0
+		}
+	}
+
+	/// Reads a two's complement integer of `n_bits` bits, extending its sign.
+	///
+	/// # Panics
+	/// The function will panic if `n_bits` is not in the range `1..=32`.
+	pub const fn read_sbit_long(&mut self, n_bits: u32) -> i32 {
+		assert!(n_bits > 0, "`n_bits` should be at least 1");
+		let value = self.read_ubit_long(n_bits);
+		let shift = 32 - n_bits;
+		((value << shift) as i32) >> shift
+	}
+
+	/// Reads an unsigned integer of `n_bits` bits.
+	///
+	/// # Panics
+	/// The function will panic if `n_bits` is greater than `64`.
+	pub const fn read_ubit_long_long(&mut self, n_bits: u32) -> u64 {
+		assert!(n_bits <= 64, "`n_bits` should be at most 64");
+		if !self.reserve(n_bits) {
+			return 0
+		}
+		if n_bits > 32 {
+			let low = self.take_bits(32) as u64;
+			low | ((self.take_bits(n_bits - 32) as u64) << 32)
+		} else {
+			self.take_bits(n_bits) as u64
+		}
+	}
+
+	/// Reads a var-int.
+	///
+	/// See also [`BitWriter::write_var_int32`](super::BitWriter::write_var_int32).
+	pub const fn read_var_int32(&mut self) -> u32 {
+		let mut result = 0;
+		let mut count = 0;
+		loop {
+			if count == MAX_VAR_INT32_BYTES {
+				return result
+			}
+			let b = self.read_byte() as u32;
+			result |= (b & 0x7f) << (7 * count);
+			count += 1;
+			if b & 0x80 == 0 {
+				return result
+			}
+		}
+	}
+
+	/// Reads a var-int.
+	///
+	/// See also [`BitWriter::write_var_int64`](super::BitWriter::write_var_int64).
+	pub const fn read_var_int64(&mut self) -> u64 {
+		let mut result = 0;
+		let mut count = 0;
+		loop {
+			if count == MAX_VAR_INT64_BYTES {
+				return result
+			}
+			let b = self.read_byte() as u64;
+			result |= (b & 0x7f) << (7 * count);
+			count += 1;
+			if b & 0x80 == 0 {
+				return result
+			}
+		}
+	}
+
+	/// Reads a zig-zag encoded var-int.
+	pub const fn read_signed_var_int32(&mut self) -> i32 {
+		zig_zag_decode32(self.read_var_int32())
+	}
+
+	/// Reads a zig-zag encoded var-int.
+	pub const fn read_signed_var_int64(&mut self) -> i64 {
+		zig_zag_decode64(self.read_var_int64())
+	}
+
+	pub const fn read_char(&mut self) -> i8 {
+		self.read_sbit_long(8) as _
+	}
+
+	pub const fn read_byte(&mut self) -> u8 {
+		self.read_ubit_long(8) as _
+	}
+
+	pub const fn read_short(&mut self) -> i16 {
+		self.read_sbit_long(16) as _
+	}
+
+	pub const fn read_word(&mut self) -> u16 {
+		self.read_ubit_long(16) as _
+	}
+
+	pub const fn read_long(&mut self) -> i32 {
+		self.read_ubit_long(32) as _
+	}
+
+	pub const fn read_long_long(&mut self) -> i64 {
+		self.read_ubit_long_long(64) as _
+	}
+
+	/// Reads the raw bits of a float.
+	pub const fn read_float(&mut self) -> f32 {
+		f32::from_bits(self.read_ubit_long(32))
+	}
+
+	/// Reads `n_bits` bits into `out`,
+	/// starting from the least-significant bit of the first byte.
+	///
+	/// Bits of `out` past `n_bits` are left untouched.
+	///
+	/// # Panics
+	/// The function will panic if `out` is shorter than `n_bits`.
+	pub const fn read_bits(&mut self, out: &mut [u8], n_bits: usize) {
+		assert!(n_bits <= out.len() * 8, "`out` should fit at least `n_bits` bits");
+		if n_bits > self.bits_left() {
+			self.set_overflowed();
+			return
+		}
+
+		let mut i = 0;
+		while i < n_bits / 8 {
+			out[i] = self.take_bits(8) as u8;
+			i += 1;
+		}
+		let rest = (n_bits % 8) as u32;
+		if rest > 0 {
+			let mask = (1u8 << rest) - 1;
+			out[i] = (out[i] & !mask) | self.take_bits(rest) as u8;
+		}
+	}
+
+	/// Fills `out` with bytes read from the buffer.
+	pub const fn read_bytes(&mut self, out: &mut [u8]) {
+		self.read_bits(out, out.len() * 8)
+	}
+
+	/// Reads a nul-terminated string into `out`.
+	///
+	/// The whole string is always consumed.
+	/// Returns `None` if the buffer overflowed,
+	/// or if `out` was too small to fit the string and its nul terminator.
+	pub fn read_string<'s>(&mut self, out: &'s mut [u8]) -> Option<&'s CStr> {
+		let mut len = 0;
+		let mut too_small = out.is_empty();
+		loop {
+			let c = self.read_byte();
+			if c == 0 {
+				break
+			}
+			if len + 1 < out.len() {
+				out[len] = c;
+				len += 1;
+			} else {
+				too_small = true;
+			}
+		}
+
+		if self.is_overflowed() || too_small {
+			if let Some(terminator) = out.get_mut(len) {
+				*terminator = 0;
+			}
+			return None
+		}
+		out[len] = 0;
+		CStr::from_bytes_with_nul(&out[..=len]).ok()
+	}
+
+	/// Reads a world coordinate.
+	///
+	/// See also [`BitWriter::write_bit_coord`](super::BitWriter::write_bit_coord).
+	pub const fn read_bit_coord(&mut self) -> f32 {
+		let has_int = self.read_one_bit();
+		let has_fract = self.read_one_bit();
+		if !has_int && !has_fract {
+			return 0.0
+		}
+
+		let sign = self.read_one_bit();
+		let int_value = if has_int { self.read_ubit_long(COORD_INTEGER_BITS) + 1 } else { 0 };
+		let fract_value = if has_fract { self.read_ubit_long(COORD_FRACTIONAL_BITS) } else { 0 };
+		let value = int_value as f32 + fract_value as f32 * COORD_RESOLUTION;
+		if sign { -value } else { value }
+	}
+
+	/// Reads a component of a unit vector.
+	///
+	/// See also [`BitWriter::write_bit_normal`](super::BitWriter::write_bit_normal).
+	pub const fn read_bit_normal(&mut self) -> f32 {
+		let sign = self.read_one_bit();
+		let value = self.read_ubit_long(NORMAL_FRACTIONAL_BITS) as f32 * NORMAL_RESOLUTION;
+		if sign { -value } else { value }
+	}
+
+	/// Reads an angle in degrees, quantized to `n_bits` bits.
+	///
+	/// # Panics
+	/// The function will panic if `n_bits` is not in the range `1..32`.
+	pub const fn read_bit_angle(&mut self, n_bits: u32) -> f32 {
+		assert!(n_bits > 0 && n_bits < 32, "`n_bits` should be in the range 1..32");
+		let shift = (1u32 << n_bits) as f32;
+		self.read_ubit_long(n_bits) as f32 * (360.0 / shift)
+	}
+
+	/// Reads a vector of world coordinates.
+	///
+	/// See also [`BitWriter::write_bit_vec3_coord`](super::BitWriter::write_bit_vec3_coord).
+	pub const fn read_bit_vec3_coord(&mut self) -> Vector {
+		let has_x = self.read_one_bit();
+		let has_y = self.read_one_bit();
+		let has_z = self.read_one_bit();
+		Vector::new(
+			if has_x { self.read_bit_coord() } else { 0.0 },
+			if has_y { self.read_bit_coord() } else { 0.0 },
+			if has_z { self.read_bit_coord() } else { 0.0 },
+		)
+	}
+
+	/// Reads angles as a vector of world coordinates.
+	///
+	/// See also [`BitWriter::write_bit_angles`](super::BitWriter::write_bit_angles).
+	pub const fn read_bit_angles(&mut self) -> QAngle {
+		let v = self.read_bit_vec3_coord();
+		QAngle::new(v.x, v.y, v.z)
+	}
+}
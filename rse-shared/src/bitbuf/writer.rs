@@ -0,0 +1,357 @@
+use ::core::{
+	ffi::{
+		CStr, c_int,
+	},
+	marker::PhantomData,
+	ptr::null,
+	slice::from_raw_parts,
+};
+use ::rse_math::{
+	QAngle, Vector,
+};
+
+use crate::cppdef::BfWrite;
+
+use super::{
+	COORD_DENOMINATOR, COORD_INTEGER_BITS, COORD_FRACTIONAL_BITS, COORD_RESOLUTION,
+	NORMAL_DENOMINATOR, NORMAL_FRACTIONAL_BITS, NORMAL_RESOLUTION,
+	zig_zag_encode32, zig_zag_encode64,
+};
+
+/// Bit-level writer over a buffer of 32-bit words,
+/// compatible with the engine's `bf_write`.
+///
+/// Writes never panic.
+/// Instead, writing past the end of the buffer sets the overflow flag,
+/// which can be checked with [`is_overflowed`](Self::is_overflowed),
+/// and the offending write is discarded.
+///
+/// # Layout
+/// This type has the exact same layout as a C++ [`bf_write`](BfWrite).
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct BitWriter<'a>(BfWrite, PhantomData<&'a mut [u32]>);
+
+impl<'a> BitWriter<'a> {
+	/// Returns a new writer that starts writing at the beginning of `data`.
+	///
+	/// # Panics
+	/// The function will panic if `data` is larger than what the engine can address in bits.
+	pub fn new(data: &'a mut [u32]) -> Self {
+		let data_bytes = c_int::try_from(data.len() * 4).ok()
+			.filter(move |&n| n <= c_int::MAX / 8)
+			.expect("buffer should be addressable in bits");
+		Self(BfWrite {
+			data: data.as_mut_ptr(),
+			data_bytes,
+			data_bits: data_bytes * 8,
+			current_bit: 0,
+			overflow: false,
+			assert_on_overflow: false,
+			debug_name: null(),
+		}, PhantomData)
+	}
+
+	/// Returns a mutable reference to a [`BitWriter`] given a raw pointer to an engine-owned [`BfWrite`].
+	///
+	/// # Safety
+	/// `ptr` must point to a valid, mutable [`bf_write`](BfWrite)
+	/// whose buffer is valid for the lifetime `'a`.
+	pub const unsafe fn from_mut_ptr(ptr: *mut BfWrite) -> &'a mut Self {
+		unsafe { &mut *(ptr as *mut Self) }
+	}
+
+	::rse_cpp::transparent_as_ptr_impls!(BitWriter for BfWrite as "bf_write");
+	::rse_cpp::transparent_inner_impls!(BitWriter for BfWrite as "bf_write");
+
+	/// Returns `true` if a write went past the end of the buffer.
+	pub const fn is_overflowed(&self) -> bool {
+		self.0.overflow
+	}
+
+	/// Returns the number of bits written so far.
+	pub const fn bits_written(&self) -> usize {
+		self.0.current_bit as _
+	}
+
+	/// Returns the number of bytes written so far, rounded up.
+	pub const fn bytes_written(&self) -> usize {
+		self.bits_written().div_ceil(8)
+	}
+
+	/// Returns the total number of bits that fit into the buffer.
+	pub const fn max_bits(&self) -> usize {
+		self.0.data_bits as _
+	}
+
+	/// Returns the number of bits that can still be written.
+	pub const fn bits_left(&self) -> usize {
+		(self.0.data_bits - self.0.current_bit) as _
+	}
+
+	/// Returns the bytes that have been written so far.
+	///
+	/// The unused bits of the last byte are zero only if the buffer was zeroed beforehand.
+	pub const fn data(&self) -> &[u8] {
+		unsafe { from_raw_parts(self.0.data as *const u8, self.bytes_written()) }
+	}
+
+	/// Moves the write position to the start of the buffer and clears the overflow flag.
+	pub const fn reset(&mut self) {
+		self.0.current_bit = 0;
+		self.0.overflow = false;
+	}
+
+	/// Moves the write position to `bit`.
+	///
+	/// Seeking past the end of the buffer sets the overflow flag.
+	pub const fn seek_to_bit(&mut self, bit: usize) {
+		if bit <= self.max_bits() {
+			self.0.current_bit = bit as _;
+		} else {
+			self.set_overflowed();
+		}
+	}
+
+	const fn set_overflowed(&mut self) {
+		self.0.current_bit = self.0.data_bits;
+		self.0.overflow = true;
+	}
+
+	/// Returns `true` if `n_bits` more bits can be written,
+	/// otherwise marks the buffer as overflowed.
+	const fn reserve(&mut self, n_bits: u32) -> bool {
+		if self.bits_left() >= n_bits as usize {
+			true
+		} else {
+			self.set_overflowed();
+			false
+		}
+	}
+
+	/// Writes the `n_bits` least-significant bits of `value` at the current position,
+	/// which must have been reserved.
+	const fn put_bits(&mut self, value: u32, n_bits: u32) {
+		let bit = self.0.current_bit as u32;
+		let shift = bit & 31;
+		let mask = if n_bits >= 32 { u32::MAX } else { (1 << n_bits) - 1 };
+		let value = value & mask;
+		unsafe {
+			let word = self.0.data.add((bit >> 5) as usize);
+			*word = ((u32::from_le(*word) & !(mask << shift)) | (value << shift)).to_le();
+			if shift + n_bits > 32 {
+				let next = word.add(1);
+				let spill = 32 - shift;
+				*next = ((u32::from_le(*next) & !(mask >> spill)) | (value >> spill)).to_le();
+			}
+		}
+		self.0.current_bit += n_bits as c_int;
+	}
+
+	/// Writes a single bit.
+	pub const fn write_one_bit(&mut self, bit: bool) {
+		if self.reserve(1) {
+			self.put_bits(bit as _, 1);
+		}
+	}
+
+	/// Writes the `n_bits` least-significant bits of `value`.
+	///
+	/// # Panics
+	/// The function will panic if `n_bits` is greater than `32`.
+	pub const fn write_ubit_long(&mut self, value: u32, n_bits: u32) {
+		assert!(n_bits <= 32, "`n_bits` should be at most 32");
+		if n_bits > 0 && self.reserve(n_bits) {
+			self.put_bits(value, n_bits);
+		}
+	}
+
+	/// Writes `value` as a two's complement integer of `n_bits` bits.
+	///
+	/// # Panics
+	/// The function will panic if `n_bits` is greater than `32`.
+	pub const fn write_sbit_long(&mut self, value: i32, n_bits: u32) {
+		self.write_ubit_long(value as u32, n_bits)
+	}
+
+	/// Writes the `n_bits` least-significant bits of `value`.
+	///
+	/// # Panics
+	/// The function will panic if `n_bits` is greater than `64`.
+	pub const fn write_ubit_long_long(&mut self, value: u64, n_bits: u32) {
+		assert!(n_bits <= 64, "`n_bits` should be at most 64");
+		if self.reserve(n_bits) {
+			if n_bits > 32 {
+				self.put_bits(value as u32, 32);
+				self.put_bits((value >> 32) as u32, n_bits - 32);
+			} else if n_bits > 0 {
+				self.put_bits(value as u32, n_bits);
+			}
+		}
+	}
+
+	/// Writes `value` as a var-int,
+	/// using 7 bits per byte and the high bit to indicate that more bytes follow.
+	pub const fn write_var_int32(&mut self, mut value: u32) {
+		while value > 0x7f {
+			self.write_byte(((value & 0x7f) | 0x80) as u8);
+			value >>= 7;
+		}
+		self.write_byte(value as u8);
+	}
+
+	/// Writes `value` as a var-int.
+	///
+	/// See also [`write_var_int32`](Self::write_var_int32).
+	pub const fn write_var_int64(&mut self, mut value: u64) {
+		while value > 0x7f {
+			self.write_byte(((value & 0x7f) | 0x80) as u8);
+			value >>= 7;
+		}
+		self.write_byte(value as u8);
+	}
+
+	/// Writes `value` as a zig-zag encoded var-int.
+	pub const fn write_signed_var_int32(&mut self, value: i32) {
+		self.write_var_int32(zig_zag_encode32(value))
+	}
+
+	/// Writes `value` as a zig-zag encoded var-int.
+	pub const fn write_signed_var_int64(&mut self, value: i64) {
+		self.write_var_int64(zig_zag_encode64(value))
+	}
+
+	pub const fn write_char(&mut self, value: i8) {
+		self.write_sbit_long(value as _, 8)
+	}
+
+	pub const fn write_byte(&mut self, value: u8) {
+		self.write_ubit_long(value as _, 8)
+	}
+
+	pub const fn write_short(&mut self, value: i16) {
+		self.write_sbit_long(value as _, 16)
+	}
+
+	pub const fn write_word(&mut self, value: u16) {
+		self.write_ubit_long(value as _, 16)
+	}
+
+	pub const fn write_long(&mut self, value: i32) {
+		self.write_sbit_long(value, 32)
+	}
+
+	pub const fn write_long_long(&mut self, value: i64) {
+		self.write_ubit_long_long(value as _, 64)
+	}
+
+	/// Writes the raw bits of `value`.
+	pub const fn write_float(&mut self, value: f32) {
+		self.write_ubit_long(value.to_bits(), 32)
+	}
+
+	/// Writes the first `n_bits` bits of `data`,
+	/// starting from the least-significant bit of the first byte.
+	///
+	/// # Panics
+	/// The function will panic if `data` is shorter than `n_bits`.
+	pub const fn write_bits(&mut self, data: &[u8], n_bits: usize) {
+		assert!(n_bits <= data.len() * 8, "`data` should contain at least `n_bits` bits");
+		if n_bits > self.bits_left() {
+			self.set_overflowed();
+			return
+		}
+
+		let mut i = 0;
+		while i < n_bits / 8 {
+			self.put_bits(data[i] as _, 8);
+			i += 1;
+		}
+		let rest = (n_bits % 8) as u32;
+		if rest > 0 {
+			self.put_bits(data[i] as _, rest);
+		}
+	}
+
+	/// Writes all of `data`.
+	pub const fn write_bytes(&mut self, data: &[u8]) {
+		self.write_bits(data, data.len() * 8)
+	}
+
+	/// Writes `s`, including its nul terminator.
+	pub const fn write_string(&mut self, s: &CStr) {
+		self.write_bytes(s.to_bytes_with_nul())
+	}
+
+	/// Writes a world coordinate,
+	/// quantized to [`COORD_RESOLUTION`] and limited to [`COORD_INTEGER_BITS`] bits of integral part.
+	pub const fn write_bit_coord(&mut self, value: f32) {
+		let sign = value <= -COORD_RESOLUTION;
+		let int_value = value.abs() as u32;
+		let fract_value = ((value * COORD_DENOMINATOR as f32) as i32).unsigned_abs() & (COORD_DENOMINATOR - 1);
+
+		self.write_one_bit(int_value != 0);
+		self.write_one_bit(fract_value != 0);
+		if int_value != 0 || fract_value != 0 {
+			self.write_one_bit(sign);
+			if int_value != 0 {
+				self.write_ubit_long(int_value - 1, COORD_INTEGER_BITS);
+			}
+			if fract_value != 0 {
+				self.write_ubit_long(fract_value, COORD_FRACTIONAL_BITS);
+			}
+		}
+	}
+
+	/// Writes a component of a unit vector in the range `[-1, 1]`,
+	/// quantized to [`NORMAL_RESOLUTION`].
+	pub const fn write_bit_normal(&mut self, value: f32) {
+		let sign = value <= -NORMAL_RESOLUTION;
+		let mut fract_value = ((value * NORMAL_DENOMINATOR as f32) as i32).unsigned_abs();
+		if fract_value > NORMAL_DENOMINATOR {
+			fract_value = NORMAL_DENOMINATOR;
+		}
+		self.write_one_bit(sign);
+		self.write_ubit_long(fract_value, NORMAL_FRACTIONAL_BITS);
+	}
+
+	/// Writes an angle in degrees, quantized to `n_bits` bits.
+	///
+	/// # Panics
+	/// The function will panic if `n_bits` is not in the range `1..32`.
+	pub const fn write_bit_angle(&mut self, degrees: f32, n_bits: u32) {
+		assert!(n_bits > 0 && n_bits < 32, "`n_bits` should be in the range 1..32");
+		let shift = 1u32 << n_bits;
+		let value = ((degrees / 360.0) * shift as f32) as i32 as u32 & (shift - 1);
+		self.write_ubit_long(value, n_bits)
+	}
+
+	/// Writes a vector of world coordinates,
+	/// where components that would be quantized to zero take up only a single bit.
+	pub const fn write_bit_vec3_coord(&mut self, v: &Vector) {
+		let components = [v.x, v.y, v.z];
+		let mut i = 0;
+		while i < 3 {
+			self.write_one_bit(!coord_is_zero(components[i]));
+			i += 1;
+		}
+		i = 0;
+		while i < 3 {
+			if !coord_is_zero(components[i]) {
+				self.write_bit_coord(components[i]);
+			}
+			i += 1;
+		}
+	}
+
+	/// Writes angles as a vector of world coordinates.
+	///
+	/// See also [`write_bit_vec3_coord`](Self::write_bit_vec3_coord).
+	pub const fn write_bit_angles(&mut self, angles: &QAngle) {
+		self.write_bit_vec3_coord(&Vector::new(angles.x, angles.y, angles.z))
+	}
+}
+
+const fn coord_is_zero(value: f32) -> bool {
+	value < COORD_RESOLUTION && value > -COORD_RESOLUTION
+}
@@ -0,0 +1,66 @@
+use ::core::ffi::c_int;
+use ::rse_cpp::{
+	AsObject, VtObjectMut, virtual_call, vt_object_wrapper,
+};
+use ::rse_math::{
+	Vector, QAngle,
+};
+
+use crate::cppdef::{
+	entities::{
+		CollideableVt, HandleEntityVt,
+	},
+	SolidType,
+};
+
+/// Safe interface to `ICollideable`, which describes the collision representation of an entity.
+pub trait CollideableImpl: AsObject<CollideableVt> {
+	/// Returns the handle entity that this collideable belongs to.
+	fn entity_handle(&self) -> VtObjectMut<HandleEntityVt> {
+		unsafe { virtual_call!(self.as_object() => get_entity_handle()) }
+	}
+	/// Returns the minimum point of the oriented bounding box, in entity space.
+	fn obb_mins(&self) -> &Vector {
+		unsafe { virtual_call!(self.as_object() => obb_mins()).as_ref() }
+	}
+	/// Returns the maximum point of the oriented bounding box, in entity space.
+	fn obb_maxs(&self) -> &Vector {
+		unsafe { virtual_call!(self.as_object() => obb_maxs()).as_ref() }
+	}
+	/// Returns the origin of the collision representation, in world space.
+	fn collision_origin(&self) -> &Vector {
+		unsafe { virtual_call!(self.as_object() => get_collision_origin()).as_ref() }
+	}
+	/// Returns the angles of the collision representation, in world space.
+	fn collision_angles(&self) -> &QAngle {
+		unsafe { virtual_call!(self.as_object() => get_collision_angles()).as_ref() }
+	}
+	fn solid_type(&self) -> SolidType {
+		unsafe { virtual_call!(self.as_object() => get_solid()) }
+	}
+	/// Returns the `FSOLID_*` flags of the collision representation.
+	fn solid_flags(&self) -> c_int {
+		unsafe { virtual_call!(self.as_object() => get_solid_flags()) }
+	}
+	fn collision_group(&self) -> c_int {
+		unsafe { virtual_call!(self.as_object() => get_collision_group()) }
+	}
+	/// Returns the minimum and maximum points of an axis-aligned box, in world space,
+	/// that surrounds the collision representation.
+	fn world_space_surrounding_bounds(&self) -> (Vector, Vector) {
+		let (mut mins, mut maxs) = (Vector::default(), Vector::default());
+		unsafe { virtual_call!(self.as_object() => world_space_surrounding_bounds(&mut mins, &mut maxs)) }
+		(mins, maxs)
+	}
+	/// Returns the minimum and maximum points of the bounds, in world space, used when touching triggers.
+	fn world_space_trigger_bounds(&self) -> (Vector, Vector) {
+		let (mut mins, mut maxs) = (Vector::default(), Vector::default());
+		unsafe { virtual_call!(self.as_object() => world_space_trigger_bounds(&mut mins, &mut maxs)) }
+		(mins, maxs)
+	}
+}
+impl<T: ?Sized + AsObject<CollideableVt>> CollideableImpl for T {}
+
+vt_object_wrapper! {
+	pub struct Collideable for CollideableVt;
+}
@@ -0,0 +1,160 @@
+use ::core::ffi::{
+	c_uint, c_ushort,
+};
+
+::rse_cpp::bitflags! {
+	/// Bit-set of the contents of a brush or an entity (`CONTENTS_*`),
+	/// also used as a mask of the contents that a trace should hit (`MASK_*`).
+	#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	#[repr(transparent)]
+	pub struct Contents: c_uint {
+		/// An eye is never valid in a solid.
+		const SOLID = 0x1;
+		/// Translucent, but not watery (glass).
+		const WINDOW = 0x2;
+		const AUX = 0x4;
+		/// Alpha-tested "grate" textures; bullets and sight pass through, but solids don't.
+		const GRATE = 0x8;
+		const SLIME = 0x10;
+		const WATER = 0x20;
+		/// Blocks AI line of sight.
+		const BLOCKLOS = 0x40;
+		/// Things that cannot be seen through (may be non-solid though).
+		const OPAQUE = 0x80;
+		const TESTFOGVOLUME = 0x100;
+		const UNUSED = 0x200;
+		const UNUSED6 = 0x400;
+		/// Per-team contents used to differentiate collisions between players and objects on different teams.
+		const TEAM1 = 0x800;
+		const TEAM2 = 0x1000;
+		/// Ignore [`OPAQUE`](Self::OPAQUE) on surfaces that have `SURF_NODRAW`.
+		const IGNORE_NODRAW_OPAQUE = 0x2000;
+		/// Hits entities which are `MOVETYPE_PUSH` (doors, plats, etc.).
+		const MOVEABLE = 0x4000;
+		/// Remaining contents are non-visible, and don't eat brushes.
+		const AREAPORTAL = 0x8000;
+		const PLAYERCLIP = 0x10000;
+		const MONSTERCLIP = 0x20000;
+		/// Currents can be added to any other contents, and may be mixed.
+		const CURRENT_0 = 0x40000;
+		const CURRENT_90 = 0x80000;
+		const CURRENT_180 = 0x100000;
+		const CURRENT_270 = 0x200000;
+		const CURRENT_UP = 0x400000;
+		const CURRENT_DOWN = 0x800000;
+		/// Removed before bsping an entity.
+		const ORIGIN = 0x1000000;
+		/// Should never be on a brush, only in game.
+		const MONSTER = 0x2000000;
+		const DEBRIS = 0x4000000;
+		/// Brushes to be added after vis leafs.
+		const DETAIL = 0x8000000;
+		/// Auto set if any surface has trans.
+		const TRANSLUCENT = 0x10000000;
+		const LADDER = 0x20000000;
+		/// Use accurate hitboxes on trace.
+		const HITBOX = 0x40000000;
+	}
+}
+
+impl Contents {
+	pub const EMPTY: Self = Self::empty();
+	pub const LAST_VISIBLE: Self = Self::OPAQUE;
+	pub const ALL_VISIBLE: Self = Self::from_bits_retain(Self::LAST_VISIBLE.bits() | (Self::LAST_VISIBLE.bits() - 1));
+
+	/// Everything.
+	pub const MASK_ALL: Self = Self::from_bits_retain(0xFFFFFFFF);
+	/// Everything that is normally solid.
+	pub const MASK_SOLID: Self = Self::SOLID.union(Self::MOVEABLE).union(Self::WINDOW).union(Self::MONSTER).union(Self::GRATE);
+	/// Everything that blocks player movement.
+	pub const MASK_PLAYERSOLID: Self = Self::MASK_SOLID.union(Self::PLAYERCLIP);
+	/// Everything that blocks NPC movement.
+	pub const MASK_NPCSOLID: Self = Self::MASK_SOLID.union(Self::MONSTERCLIP);
+	/// Water physics in these contents.
+	pub const MASK_WATER: Self = Self::WATER.union(Self::MOVEABLE).union(Self::SLIME);
+	/// Everything that blocks lighting.
+	pub const MASK_OPAQUE: Self = Self::SOLID.union(Self::MOVEABLE).union(Self::OPAQUE);
+	/// Everything that blocks lighting, but with monsters added.
+	pub const MASK_OPAQUE_AND_NPCS: Self = Self::MASK_OPAQUE.union(Self::MONSTER);
+	/// Everything that blocks line of sight for AI.
+	pub const MASK_BLOCKLOS: Self = Self::SOLID.union(Self::MOVEABLE).union(Self::BLOCKLOS);
+	/// Everything that blocks line of sight for AI, plus NPCs.
+	pub const MASK_BLOCKLOS_AND_NPCS: Self = Self::MASK_BLOCKLOS.union(Self::MONSTER);
+	/// Everything that blocks line of sight for players.
+	pub const MASK_VISIBLE: Self = Self::MASK_OPAQUE.union(Self::IGNORE_NODRAW_OPAQUE);
+	/// Everything that blocks line of sight for players, but with monsters added.
+	pub const MASK_VISIBLE_AND_NPCS: Self = Self::MASK_OPAQUE_AND_NPCS.union(Self::IGNORE_NODRAW_OPAQUE);
+	/// Bullets see these as solid.
+	pub const MASK_SHOT: Self = Self::SOLID.union(Self::MOVEABLE).union(Self::MONSTER).union(Self::WINDOW).union(Self::DEBRIS).union(Self::HITBOX);
+	/// Non-raycasted weapons see these as solid (includes grates).
+	pub const MASK_SHOT_HULL: Self = Self::SOLID.union(Self::MOVEABLE).union(Self::MONSTER).union(Self::WINDOW).union(Self::DEBRIS).union(Self::GRATE);
+	/// Hits solids (not grates) and passes through everything else.
+	pub const MASK_SHOT_PORTAL: Self = Self::SOLID.union(Self::MOVEABLE).union(Self::WINDOW).union(Self::MONSTER);
+	/// Everything normally solid, except monsters (world + brush only).
+	pub const MASK_SOLID_BRUSHONLY: Self = Self::SOLID.union(Self::MOVEABLE).union(Self::WINDOW).union(Self::GRATE);
+	/// Everything normally solid for player movement, except monsters (world + brush only).
+	pub const MASK_PLAYERSOLID_BRUSHONLY: Self = Self::MASK_SOLID_BRUSHONLY.union(Self::PLAYERCLIP);
+	/// Everything normally solid for NPC movement, except monsters (world + brush only).
+	pub const MASK_NPCSOLID_BRUSHONLY: Self = Self::MASK_SOLID_BRUSHONLY.union(Self::MONSTERCLIP);
+	/// Just the world, used for route rebuilding.
+	pub const MASK_NPCWORLDSTATIC: Self = Self::SOLID.union(Self::WINDOW).union(Self::MONSTERCLIP).union(Self::GRATE);
+	/// These are things that can split area portals.
+	pub const MASK_SPLITAREAPORTAL: Self = Self::WATER.union(Self::SLIME);
+	/// All currents.
+	pub const MASK_CURRENT: Self = Self::CURRENT_0.union(Self::CURRENT_90).union(Self::CURRENT_180)
+		.union(Self::CURRENT_270).union(Self::CURRENT_UP).union(Self::CURRENT_DOWN);
+	/// Everything that blocks corpse movement.
+	pub const MASK_DEADSOLID: Self = Self::SOLID.union(Self::PLAYERCLIP).union(Self::WINDOW).union(Self::GRATE);
+}
+
+::rse_cpp::bitflags! {
+	/// Bit-set of the properties of a surface (`SURF_*`).
+	#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	#[repr(transparent)]
+	pub struct SurfFlags: c_ushort {
+		/// Value will hold the light strength.
+		const LIGHT = 0x1;
+		/// Don't draw, indicates we should skylight and draw 2D sky but not draw the 3D skybox.
+		const SKY2D = 0x2;
+		/// Don't draw, but add to skybox.
+		const SKY = 0x4;
+		/// Turbulent water warp.
+		const WARP = 0x8;
+		const TRANS = 0x10;
+		/// The surface can not have a portal placed on it.
+		const NOPORTAL = 0x20;
+		/// Xbox hack to work around elimination of trigger surfaces, which breaks occluders.
+		const TRIGGER = 0x40;
+		/// Don't bother referencing the texture.
+		const NODRAW = 0x80;
+		/// Make a primary BSP splitter.
+		const HINT = 0x100;
+		/// Completely ignore, allowing non-closed brushes.
+		const SKIP = 0x200;
+		/// Don't calculate light.
+		const NOLIGHT = 0x400;
+		/// Calculate three lightmaps for the surface for bumpmapping.
+		const BUMPLIGHT = 0x800;
+		/// Don't receive shadows.
+		const NOSHADOWS = 0x1000;
+		/// Don't receive decals.
+		const NODECALS = 0x2000;
+		/// Don't subdivide patches on this surface.
+		const NOCHOP = 0x4000;
+		/// Surface is part of a hitbox.
+		const HITBOX = 0x8000;
+	}
+}
+
+::rse_cpp::bitflags! {
+	/// Bit-set of the properties of a displacement surface (`DISPSURF_FLAG_*`).
+	#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	#[repr(transparent)]
+	pub struct DispSurfFlags: c_ushort {
+		const SURFACE = 1 << 0;
+		const WALKABLE = 1 << 1;
+		const BUILDABLE = 1 << 2;
+		const SURFPROP1 = 1 << 3;
+		const SURFPROP2 = 1 << 4;
+	}
+}
@@ -1,2 +1,96 @@
-// TODO: More stuff related to `CRC32_t`?
+/// CRC32 checksum (`CRC32_t`), as computed by the engine.
 pub type Crc32 = ::core::ffi::c_uint;
+
+const CRC32_INIT_VALUE: Crc32 = 0xFFFFFFFF;
+const CRC32_XOR_VALUE: Crc32 = 0xFFFFFFFF;
+
+static CRC32_TABLE: [Crc32; 256] = {
+	let mut table = [0; 256];
+	let mut i = 0;
+	while i < 256 {
+		let mut crc = i as Crc32;
+		let mut bit = 0;
+		while bit < 8 {
+			crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+			bit += 1;
+		}
+		table[i] = crc;
+		i += 1;
+	}
+	table
+};
+
+/// Initializes `crc` for processing buffers (`CRC32_Init`).
+pub const fn crc32_init(crc: &mut Crc32) {
+	*crc = CRC32_INIT_VALUE;
+}
+
+/// Updates `crc` with the contents of `buffer` (`CRC32_ProcessBuffer`).
+pub fn crc32_process_buffer(crc: &mut Crc32, buffer: &[u8]) {
+	*crc = buffer.iter().fold(*crc, move |crc, &b| CRC32_TABLE[((crc ^ b as Crc32) & 0xFF) as usize] ^ (crc >> 8));
+}
+
+/// Finishes computing `crc` (`CRC32_Final`).
+pub const fn crc32_final(crc: &mut Crc32) {
+	*crc ^= CRC32_XOR_VALUE;
+}
+
+/// Returns the CRC32 checksum of `buffer` (`CRC32_ProcessSingleBuffer`).
+pub fn crc32(buffer: &[u8]) -> Crc32 {
+	let mut hasher = Crc32Hasher::new();
+	hasher.update(buffer);
+	hasher.finish()
+}
+
+/// Streaming CRC32 hasher that produces the same values as the engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Crc32Hasher {
+	crc: Crc32,
+}
+
+impl Default for Crc32Hasher {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Crc32Hasher {
+	pub const fn new() -> Self {
+		Self {
+			crc: CRC32_INIT_VALUE,
+		}
+	}
+
+	/// Updates the checksum with the contents of `buffer`.
+	pub fn update(&mut self, buffer: &[u8]) {
+		crc32_process_buffer(&mut self.crc, buffer)
+	}
+
+	/// Returns the checksum of everything processed so far.
+	pub const fn finish(&self) -> Crc32 {
+		self.crc ^ CRC32_XOR_VALUE
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn checksums() {
+		assert_eq!(crc32(b""), 0);
+		assert_eq!(crc32(b"123456789"), 0xCBF43926);
+
+		let mut crc = 0;
+		crc32_init(&mut crc);
+		crc32_process_buffer(&mut crc, b"12345");
+		crc32_process_buffer(&mut crc, b"6789");
+		crc32_final(&mut crc);
+		assert_eq!(crc, 0xCBF43926);
+
+		let mut hasher = Crc32Hasher::new();
+		hasher.update(b"The quick brown fox ");
+		hasher.update(b"jumps over the lazy dog");
+		assert_eq!(hasher.finish(), 0x414FA339);
+	}
+}
@@ -1,5 +1,5 @@
 use ::core::ffi::{
-	c_char, c_float, c_int, c_short, c_uint, c_ulong, c_ushort, c_void,
+	c_char, c_float, c_int, c_short, c_ulong, c_ushort, c_void,
 };
 use ::rse_cpp::{
 	vtable, VtObjectMut, VtObjectPtr, RefConst, RefMut,
@@ -10,7 +10,7 @@ use ::rse_math::{
 
 use super::{
 	model_t, SolidType,
-	Ray, Trace,
+	Contents, Ray, Trace,
 	string_t,
 };
 
@@ -98,9 +98,7 @@ pub struct ServerEntityVt {
 
 vtable! {
 	pub ServerEntityVtBase for VtObjectPtr<ServerEntityVt> {
-		pub fn destructor();
-		#[cfg(not(windows))]
-		pub fn destructor_2();
+		// The destructor overrides the one of `IHandleEntity`, so it doesn't get new slots.
 		pub fn get_model_index() -> c_int;
 		pub fn get_model_name() -> string_t;
 		pub fn set_model_index(index: c_int);
@@ -134,8 +132,8 @@ vtable! {
 		pub fn obb_mins() -> RefConst<Vector>;
 		pub fn obb_maxs() -> RefConst<Vector>;
 		pub fn world_space_trigger_bounds(out_world_mins: *mut Vector, out_world_maxs: *mut Vector);
-		pub fn test_collision(ray: RefConst<Ray>, contents_mask: c_uint, out_trace: RefMut<Trace>) -> bool;
-		pub fn test_hitboxes(ray: RefConst<Ray>, contents_mask: c_uint, out_trace: RefMut<Trace>) -> bool;
+		pub fn test_collision(ray: RefConst<Ray>, contents_mask: Contents, out_trace: RefMut<Trace>) -> bool;
+		pub fn test_hitboxes(ray: RefConst<Ray>, contents_mask: Contents, out_trace: RefMut<Trace>) -> bool;
 		pub fn get_collision_model_index() -> c_int;
 		pub fn get_collision_model() -> *const model_t;
 		pub fn get_collision_origin() -> RefConst<Vector>;
@@ -194,8 +192,22 @@ impl BaseHandle {
 	pub const fn serial_number(&self) -> c_ulong {
 		self.index >> Self::NUM_ENT_ENTRY_BITS
 	}
+
+	/// Returns the part of the serial number that is stored in
+	/// [`BaseEdict::network_serial_number`] of the entity's edict.
+	pub const fn network_serial_number(&self) -> c_short {
+		(self.serial_number() & ((1 << NUM_NETWORKED_EHANDLE_SERIAL_NUMBER_BITS) - 1)) as _
+	}
+
+	/// Returns `true` if this isn't [`INVALID`](Self::INVALID).
+	pub const fn is_valid(&self) -> bool {
+		self.index != Self::INVALID.index
+	}
 }
 
+/// Number of bits of the serial numbers of entity handles that are networked.
+pub const NUM_NETWORKED_EHANDLE_SERIAL_NUMBER_BITS: u8 = 10;
+
 vtable! {
 	pub HandleEntityVt {
 		pub fn destructor();
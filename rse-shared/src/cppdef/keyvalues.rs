@@ -1,2 +1,113 @@
-// TODO: `KeyValues`.
-pub type KeyValues = ::core::ffi::c_void;
+use ::core::ffi::{
+	CStr, c_char, c_float, c_int, c_uchar, c_void,
+};
+use ::rse_cpp::vtable;
+
+use super::wchar_t;
+
+/// Handle to a key name string interned by `IKeyValuesSystem`.
+pub type HKeySymbol = c_int;
+pub const INVALID_KEY_SYMBOL: HKeySymbol = -1;
+
+/// Type of the value stored in a [`KeyValues`] node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum KeyValuesType {
+	/// No value; the node may have subkeys.
+	None = 0,
+	String = 1,
+	Int = 2,
+	Float = 3,
+	Ptr = 4,
+	WString = 5,
+	Color = 6,
+	Uint64 = 7,
+	/// Number of types, also used as an end marker in the binary format.
+	NumTypes = 8,
+}
+
+impl KeyValuesType {
+	/// Returns the type corresponding to a raw type tag, if there is one.
+	pub const fn from_raw(raw: u8) -> Option<Self> {
+		Some(match raw {
+			0 => Self::None,
+			1 => Self::String,
+			2 => Self::Int,
+			3 => Self::Float,
+			4 => Self::Ptr,
+			5 => Self::WString,
+			6 => Self::Color,
+			7 => Self::Uint64,
+			8 => Self::NumTypes,
+			_ => return None,
+		})
+	}
+}
+
+/// Value of a [`KeyValues`] node that isn't a string.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub union KeyValuesData {
+	pub int_value: c_int,
+	pub float_value: c_float,
+	pub ptr_value: *mut c_void,
+	pub color: [c_uchar; 4],
+}
+
+/// Node of a recursive data structure, used for storing key-value pairs,
+/// where values can be nodes that themselves have subkeys.
+///
+/// Nodes with the same parent are linked through [`peer`](Self::peer),
+/// and the first subkey of a node is [`sub`](Self::sub).
+///
+/// Memory for a node is allocated with `IKeyValuesSystem::AllocKeyValuesMemory`,
+/// while [`string_value`](Self::string_value) and [`wstring_value`](Self::wstring_value)
+/// are allocated with `new[]`.
+// Cross-referenced with `KeyValues.h` in `tier1`.
+#[repr(C)]
+pub struct KeyValues {
+	pub key_name: HKeySymbol,
+	/// String value for [`KeyValuesType::String`],
+	/// or a pointer to 8 bytes for [`KeyValuesType::Uint64`].
+	pub string_value: *mut c_char,
+	pub wstring_value: *mut wchar_t,
+	pub data: KeyValuesData,
+	/// Raw [`KeyValuesType`] of the value, which is written by the engine.
+	///
+	/// See [`data_type`](Self::data_type).
+	pub data_type: c_char,
+	pub has_escape_sequences: bool,
+	pub evaluate_conditionals: bool,
+	pub unused: [c_char; 1],
+	pub peer: *mut KeyValues,
+	pub sub: *mut KeyValues,
+	pub chain: *mut KeyValues,
+}
+
+impl KeyValues {
+	/// Returns the type of the value, or `None` if [`data_type`](Self::data_type) isn't a known type.
+	pub const fn data_type(&self) -> Option<KeyValuesType> {
+		KeyValuesType::from_raw(self.data_type as u8)
+	}
+}
+
+pub const KEYVALUESSYSTEM_INTERFACE_VERSION: &CStr = c"KeyValuesSystem002";
+
+vtable! {
+	pub KeyValuesSystemVt {
+		pub fn register_sizeof_key_values(size: c_int);
+		pub fn alloc_key_values_memory(size: c_int) -> *mut c_void;
+		pub fn free_key_values_memory(mem: *mut c_void);
+		pub fn get_symbol_for_string(name: *const c_char, create: bool) -> HKeySymbol;
+		pub fn get_string_for_symbol(symbol: HKeySymbol) -> *const c_char;
+		pub fn add_key_values_to_memory_leak_list(mem: *mut c_void, name: HKeySymbol);
+		pub fn remove_key_values_from_memory_leak_list(mem: *mut c_void);
+		pub fn add_file_key_values_to_cache(kv: *const KeyValues, resource_name: *const c_char, path_id: *const c_char);
+		pub fn load_file_key_values_from_cache(
+			out_kv: *mut KeyValues, resource_name: *const c_char, path_id: *const c_char,
+			file_system: *mut c_void,
+		) -> bool;
+		pub fn invalidate_cache();
+		pub fn invalidate_cache_for_file(resource_name: *const c_char, path_id: *const c_char);
+	}
+}
@@ -1,7 +1,14 @@
-// TODO: `wchar_t`.
+/// C++ wide character, which is a UTF-16 code unit on Windows.
+#[cfg(windows)]
 #[allow(non_camel_case_types)]
 pub type wchar_t = u16;
+/// C++ wide character, which is a UTF-32 code point outside of Windows.
+#[cfg(not(windows))]
+#[allow(non_camel_case_types)]
+pub type wchar_t = u32;
 
+mod bsp_flags;
+pub use bsp_flags::*;
 mod buffer;
 pub use buffer::*;
 mod client_textmessage;
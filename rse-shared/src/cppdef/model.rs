@@ -1,7 +1,11 @@
 use ::core::ffi::{
-	c_char, c_short, c_ushort, c_void,
+	c_char, c_short, c_void,
 };
-use ::rse_math::VectorAligned;
+use ::rse_math::{
+	Vector, VectorAligned,
+};
+
+use super::SurfFlags;
 
 // `struct model_t` is never implemented, only used behind a pointer.
 #[repr(transparent)]
@@ -14,7 +18,7 @@ pub struct model_t(c_void);
 pub struct Surface {
 	pub name: *const c_char,
 	pub surface_props: c_short,
-	pub flags: c_ushort,
+	pub flags: SurfFlags,
 }
 
 #[derive(Debug)]
@@ -27,3 +31,76 @@ pub struct Ray {
 	pub is_ray: bool,
 	pub is_swept: bool,
 }
+
+impl Ray {
+	/// Returns a ray for a line trace from `start` to `end`.
+	pub fn line(start: Vector, end: Vector) -> Self {
+		let delta = sub(end, start);
+		Self {
+			start: start.into(),
+			delta: delta.into(),
+			start_offset: VectorAligned::default(),
+			extents: VectorAligned::default(),
+			is_ray: true,
+			is_swept: length_sqr(delta) != 0.0,
+		}
+	}
+
+	/// Returns a ray for sweeping the box from `mins` to `maxs`, relative to `start`, from `start` to `end`.
+	pub fn hull(start: Vector, end: Vector, mins: Vector, maxs: Vector) -> Self {
+		let delta = sub(end, start);
+		let extents = scale(sub(maxs, mins), 0.5);
+		let start_offset = scale(add(mins, maxs), 0.5);
+		Self {
+			start: add(start, start_offset).into(),
+			delta: delta.into(),
+			start_offset: scale(start_offset, -1.0).into(),
+			extents: extents.into(),
+			is_ray: length_sqr(extents) < 1e-6,
+			is_swept: length_sqr(delta) != 0.0,
+		}
+	}
+
+	/// Returns the point where the ray starts, without the offset of the box.
+	pub fn start_point(&self) -> Vector {
+		add(*self.start, *self.start_offset)
+	}
+
+	/// Returns the point where the ray ends, without the offset of the box.
+	pub fn end_point(&self) -> Vector {
+		add(self.start_point(), *self.delta)
+	}
+}
+
+const fn add(a: Vector, b: Vector) -> Vector {
+	Vector::new(a.x + b.x, a.y + b.y, a.z + b.z)
+}
+const fn sub(a: Vector, b: Vector) -> Vector {
+	Vector::new(a.x - b.x, a.y - b.y, a.z - b.z)
+}
+const fn scale(v: Vector, s: f32) -> Vector {
+	Vector::new(v.x * s, v.y * s, v.z * s)
+}
+const fn length_sqr(v: Vector) -> f32 {
+	v.x * v.x + v.y * v.y + v.z * v.z
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn ray_hull() {
+		let start = Vector::new(0.0, 0.0, 0.0);
+		let end = Vector::new(100.0, 0.0, 0.0);
+		let ray = Ray::hull(start, end, Vector::new(-16.0, -16.0, 0.0), Vector::new(16.0, 16.0, 72.0));
+		assert!(!ray.is_ray && ray.is_swept);
+		assert_eq!(*ray.extents, Vector::new(16.0, 16.0, 36.0));
+		assert_eq!(*ray.start, Vector::new(0.0, 0.0, 36.0));
+		assert_eq!(ray.start_point(), start);
+		assert_eq!(ray.end_point(), end);
+
+		let ray = Ray::line(start, start);
+		assert!(ray.is_ray && !ray.is_swept);
+	}
+}
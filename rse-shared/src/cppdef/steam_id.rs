@@ -1,3 +1,102 @@
+use ::core::{
+	fmt,
+	str::FromStr,
+};
+
+/// Type of a Steam account (`EAccountType`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum AccountType {
+	Invalid = 0,
+	Individual = 1,
+	Multiseat = 2,
+	GameServer = 3,
+	AnonGameServer = 4,
+	Pending = 5,
+	ContentServer = 6,
+	Clan = 7,
+	Chat = 8,
+	ConsoleUser = 9,
+	AnonUser = 10,
+}
+
+impl AccountType {
+	pub const fn from_raw(raw: u8) -> Option<Self> {
+		Some(match raw {
+			0 => Self::Invalid,
+			1 => Self::Individual,
+			2 => Self::Multiseat,
+			3 => Self::GameServer,
+			4 => Self::AnonGameServer,
+			5 => Self::Pending,
+			6 => Self::ContentServer,
+			7 => Self::Clan,
+			8 => Self::Chat,
+			9 => Self::ConsoleUser,
+			10 => Self::AnonUser,
+			_ => return None,
+		})
+	}
+
+	/// Returns the letter used for the account type in the Steam3 format.
+	///
+	/// Chat accounts may also use `c` or `L` depending on their instance,
+	/// see [`SteamId::steam3`].
+	pub const fn letter(self) -> char {
+		match self {
+			Self::Invalid => 'I',
+			Self::Individual => 'U',
+			Self::Multiseat => 'M',
+			Self::GameServer => 'G',
+			Self::AnonGameServer => 'A',
+			Self::Pending => 'P',
+			Self::ContentServer => 'C',
+			Self::Clan => 'g',
+			Self::Chat => 'T',
+			Self::ConsoleUser => 'i',
+			Self::AnonUser => 'a',
+		}
+	}
+}
+
+/// Steam universe (`EUniverse`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum Universe {
+	Invalid = 0,
+	Public = 1,
+	Beta = 2,
+	Internal = 3,
+	Dev = 4,
+}
+
+impl Universe {
+	pub const fn from_raw(raw: u8) -> Option<Self> {
+		Some(match raw {
+			0 => Self::Invalid,
+			1 => Self::Public,
+			2 => Self::Beta,
+			3 => Self::Internal,
+			4 => Self::Dev,
+			_ => return None,
+		})
+	}
+}
+
+/// Instance of individual accounts logged in from the desktop client.
+pub const STEAM_USER_DESKTOP_INSTANCE: u32 = 1;
+/// Instance of individual accounts logged in from a console.
+pub const STEAM_USER_CONSOLE_INSTANCE: u32 = 2;
+/// Instance of individual accounts logged in from the web.
+pub const STEAM_USER_WEB_INSTANCE: u32 = 4;
+
+/// Instance flag of chat accounts that belong to a clan.
+pub const CHAT_INSTANCE_FLAG_CLAN: u32 = (SteamId::INSTANCE_MASK + 1) >> 1;
+/// Instance flag of chat accounts that are lobbies.
+pub const CHAT_INSTANCE_FLAG_LOBBY: u32 = (SteamId::INSTANCE_MASK + 1) >> 2;
+/// Instance flag of chat accounts that are matchmaking lobbies.
+pub const CHAT_INSTANCE_FLAG_MMS_LOBBY: u32 = (SteamId::INSTANCE_MASK + 1) >> 3;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(C)]
 pub struct SteamId {
@@ -10,10 +109,42 @@ impl SteamId {
 	const ACCOUNT_TYPE_BITS: u32 = 4;
 	const UNIVERSE_BITS: u32 = 8;
 
+	const INSTANCE_MASK: u32 = Self::u64_mask(Self::INSTANCE_ID_BITS) as u32;
+
 	const fn u64_mask(bits: u32) -> u64 {
 		u64::MAX >> (u64::BITS - bits)
 	}
 
+	pub const fn from_bits(bits: u64) -> Self {
+		Self {
+			bits,
+		}
+	}
+
+	/// Returns a SteamID built from its components.
+	///
+	/// `instance` is truncated to 20 bits.
+	pub const fn new(account_id: u32, instance: u32, account_type: AccountType, universe: Universe) -> Self {
+		Self::from_raw_parts(account_id, instance, account_type as u8, universe as u8)
+	}
+
+	/// Returns a SteamID built from its raw components.
+	///
+	/// `instance` is truncated to 20 bits, and `account_type` to 4 bits.
+	pub const fn from_raw_parts(account_id: u32, instance: u32, account_type: u8, universe: u8) -> Self {
+		Self::from_bits(
+			account_id as u64 |
+			((instance as u64 & Self::u64_mask(Self::INSTANCE_ID_BITS)) << Self::ACCOUNT_ID_BITS) |
+			((account_type as u64 & Self::u64_mask(Self::ACCOUNT_TYPE_BITS)) << (Self::ACCOUNT_ID_BITS + Self::INSTANCE_ID_BITS)) |
+			((universe as u64) << (Self::ACCOUNT_ID_BITS + Self::INSTANCE_ID_BITS + Self::ACCOUNT_TYPE_BITS))
+		)
+	}
+
+	/// Returns the SteamID of an individual account logged in from the desktop client.
+	pub const fn individual(account_id: u32, universe: Universe) -> Self {
+		Self::new(account_id, STEAM_USER_DESKTOP_INSTANCE, AccountType::Individual, universe)
+	}
+
 	pub const fn account_id(&self) -> u32 {
 		(self.bits & Self::u64_mask(Self::ACCOUNT_ID_BITS)) as _
 	}
@@ -22,14 +153,270 @@ impl SteamId {
 		((self.bits >> Self::ACCOUNT_ID_BITS) & Self::u64_mask(Self::INSTANCE_ID_BITS)) as _
 	}
 
-	pub const fn account_type(&self) -> u8 {
+	pub const fn raw_account_type(&self) -> u8 {
 		((self.bits >> (Self::ACCOUNT_ID_BITS + Self::INSTANCE_ID_BITS)) & Self::u64_mask(Self::ACCOUNT_TYPE_BITS)) as _
 	}
 
-	pub const fn universe(&self) -> u8 {
+	/// Returns the account type, or `None` if it's not a known [`AccountType`].
+	pub const fn account_type(&self) -> Option<AccountType> {
+		AccountType::from_raw(self.raw_account_type())
+	}
+
+	pub const fn raw_universe(&self) -> u8 {
 		(
 			(self.bits >> (Self::ACCOUNT_ID_BITS + Self::INSTANCE_ID_BITS + Self::ACCOUNT_TYPE_BITS)) &
 			Self::u64_mask(Self::UNIVERSE_BITS)
 		) as _
 	}
+
+	/// Returns the universe, or `None` if it's not a known [`Universe`].
+	pub const fn universe(&self) -> Option<Universe> {
+		Universe::from_raw(self.raw_universe())
+	}
+
+	/// Returns `true` if the SteamID could belong to an existing account,
+	/// like `CSteamID::IsValid`.
+	pub const fn is_valid(&self) -> bool {
+		let Some(universe) = self.universe() else { return false };
+		if matches!(universe, Universe::Invalid) {
+			return false
+		}
+		match self.account_type() {
+			None | Some(AccountType::Invalid) => false,
+			Some(AccountType::Individual) => self.account_id() != 0 && self.instance_id() <= STEAM_USER_WEB_INSTANCE,
+			Some(AccountType::Clan) => self.account_id() != 0 && self.instance_id() == 0,
+			Some(AccountType::GameServer) => self.account_id() != 0,
+			Some(_) => true,
+		}
+	}
+
+	/// Returns `true` if the SteamID belongs to a person.
+	pub const fn is_individual_account(&self) -> bool {
+		matches!(self.account_type(), Some(AccountType::Individual | AccountType::ConsoleUser))
+	}
+
+	/// Returns `true` if the SteamID belongs to a persistent or anonymous game server.
+	pub const fn is_game_server_account(&self) -> bool {
+		matches!(self.account_type(), Some(AccountType::GameServer | AccountType::AnonGameServer))
+	}
+
+	/// Returns `true` if the SteamID belongs to an anonymous user or game server.
+	pub const fn is_anon_account(&self) -> bool {
+		matches!(self.account_type(), Some(AccountType::AnonUser | AccountType::AnonGameServer))
+	}
+
+	pub const fn is_clan_account(&self) -> bool {
+		matches!(self.account_type(), Some(AccountType::Clan))
+	}
+
+	pub const fn is_chat_account(&self) -> bool {
+		matches!(self.account_type(), Some(AccountType::Chat))
+	}
+
+	pub const fn is_lobby(&self) -> bool {
+		self.is_chat_account() && (self.instance_id() & CHAT_INSTANCE_FLAG_LOBBY) != 0
+	}
+
+	/// Returns an object that formats the SteamID in the Steam2 format, like `STEAM_1:0:11101`.
+	///
+	/// The universe is written as-is,
+	/// even though older games write `0` for the public universe.
+	pub const fn steam2(&self) -> Steam2Display {
+		Steam2Display(*self)
+	}
+
+	/// Returns an object that formats the SteamID in the Steam3 format, like `[U:1:22202]`.
+	pub const fn steam3(&self) -> Steam3Display {
+		Steam3Display(*self)
+	}
+}
+
+impl From<u64> for SteamId {
+	fn from(bits: u64) -> Self {
+		Self::from_bits(bits)
+	}
+}
+
+impl From<SteamId> for u64 {
+	fn from(id: SteamId) -> Self {
+		id.bits
+	}
+}
+
+/// Error returned when parsing a [`SteamId`] fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ::thiserror::Error)]
+#[error("invalid SteamID")]
+pub struct ParseSteamIdError;
+
+impl FromStr for SteamId {
+	type Err = ParseSteamIdError;
+	/// Parses a SteamID in the Steam2 (`STEAM_1:0:11101`), Steam3 (`[U:1:22202]`)
+	/// or 64-bit (`76561197960287930`) format.
+	///
+	/// In the Steam2 format, a universe of `0` is treated as [`Universe::Public`].
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		if let Some(rest) = s.strip_prefix("STEAM_") {
+			parse_steam2(rest)
+		} else if s.starts_with(|c: char| c.is_ascii_digit()) {
+			parse_number(s).map(Self::from_bits)
+		} else {
+			let inner = s.strip_prefix('[').and_then(move |s| s.strip_suffix(']')).unwrap_or(s);
+			parse_steam3(inner)
+		}
+	}
+}
+
+fn parse_number<T: FromStr>(s: &str) -> Result<T, ParseSteamIdError> {
+	if s.is_empty() || !s.bytes().all(move |c| c.is_ascii_digit()) {
+		return Err(ParseSteamIdError)
+	}
+	s.parse().map_err(move |_| ParseSteamIdError)
+}
+
+fn parse_steam2(s: &str) -> Result<SteamId, ParseSteamIdError> {
+	let mut parts = s.split(':');
+	let (Some(universe), Some(y), Some(z), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+		return Err(ParseSteamIdError)
+	};
+	let universe = match parse_number(universe)? {
+		0 => Universe::Public as u8,
+		universe => universe,
+	};
+	let y: u32 = parse_number(y)?;
+	let z: u32 = parse_number(z)?;
+	if y > 1 {
+		return Err(ParseSteamIdError)
+	}
+	let account_id = z.checked_mul(2).and_then(move |id| id.checked_add(y)).ok_or(ParseSteamIdError)?;
+	Ok(SteamId::from_raw_parts(account_id, STEAM_USER_DESKTOP_INSTANCE, AccountType::Individual as u8, universe))
+}
+
+fn parse_steam3(s: &str) -> Result<SteamId, ParseSteamIdError> {
+	let mut parts = s.split(':');
+	let (Some(letter), Some(universe), Some(account_id)) = (parts.next(), parts.next(), parts.next()) else {
+		return Err(ParseSteamIdError)
+	};
+	let instance = parts.next().map(parse_number::<u32>).transpose()?;
+	if parts.next().is_some() {
+		return Err(ParseSteamIdError)
+	}
+
+	let (account_type, flags, default_instance) = match letter {
+		"I" => (AccountType::Invalid, 0, 0),
+		"U" => (AccountType::Individual, 0, STEAM_USER_DESKTOP_INSTANCE),
+		"M" => (AccountType::Multiseat, 0, 0),
+		"G" => (AccountType::GameServer, 0, 0),
+		"A" => (AccountType::AnonGameServer, 0, 0),
+		"P" => (AccountType::Pending, 0, 0),
+		"C" => (AccountType::ContentServer, 0, 0),
+		"g" => (AccountType::Clan, 0, 0),
+		"T" => (AccountType::Chat, 0, 0),
+		"i" => (AccountType::ConsoleUser, 0, 0),
+		"c" => (AccountType::Chat, CHAT_INSTANCE_FLAG_CLAN, 0),
+		"L" => (AccountType::Chat, CHAT_INSTANCE_FLAG_LOBBY, 0),
+		"a" => (AccountType::AnonUser, 0, 0),
+		_ => return Err(ParseSteamIdError),
+	};
+	let instance = instance.unwrap_or(default_instance);
+	if instance > SteamId::INSTANCE_MASK {
+		return Err(ParseSteamIdError)
+	}
+	Ok(SteamId::from_raw_parts(
+		parse_number(account_id)?, instance | flags, account_type as u8, parse_number(universe)?,
+	))
+}
+
+/// Formats a [`SteamId`] in the Steam2 format.
+///
+/// See [`SteamId::steam2`].
+#[derive(Debug, Clone, Copy)]
+pub struct Steam2Display(SteamId);
+
+impl fmt::Display for Steam2Display {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let id = self.0.account_id();
+		write!(f, "STEAM_{}:{}:{}", self.0.raw_universe(), id & 1, id >> 1)
+	}
+}
+
+/// Formats a [`SteamId`] in the Steam3 format.
+///
+/// See [`SteamId::steam3`].
+#[derive(Debug, Clone, Copy)]
+pub struct Steam3Display(SteamId);
+
+impl fmt::Display for Steam3Display {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let id = self.0;
+		let mut instance = id.instance_id();
+		let letter = match id.account_type() {
+			Some(AccountType::Chat) if instance & CHAT_INSTANCE_FLAG_CLAN != 0 => {
+				instance &= !CHAT_INSTANCE_FLAG_CLAN;
+				'c'
+			}
+			Some(AccountType::Chat) if instance & CHAT_INSTANCE_FLAG_LOBBY != 0 => {
+				instance &= !CHAT_INSTANCE_FLAG_LOBBY;
+				'L'
+			}
+			Some(account_type) => account_type.letter(),
+			None => 'i',
+		};
+		write!(f, "[{letter}:{}:{}", id.raw_universe(), id.account_id())?;
+		let default_instance = match id.account_type() {
+			Some(AccountType::Individual) => STEAM_USER_DESKTOP_INSTANCE,
+			_ => 0,
+		};
+		if instance != default_instance || id.account_type() == Some(AccountType::AnonGameServer) {
+			write!(f, ":{instance}")?;
+		}
+		f.write_str("]")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ::alloc::string::ToString;
+
+	use super::*;
+
+	#[test]
+	fn formats() {
+		let id = SteamId::individual(22202, Universe::Public);
+		assert_eq!(id.bits, 76561197960287930);
+		assert_eq!(id.steam2().to_string(), "STEAM_1:0:11101");
+		assert_eq!(id.steam3().to_string(), "[U:1:22202]");
+		assert_eq!("STEAM_0:0:11101".parse(), Ok(id));
+		assert_eq!("[U:1:22202]".parse(), Ok(id));
+		assert_eq!("U:1:22202".parse(), Ok(id));
+		assert_eq!("76561197960287930".parse(), Ok(id));
+		assert!(id.is_valid() && id.is_individual_account());
+
+		let server = SteamId::new(1234, 5, AccountType::AnonGameServer, Universe::Public);
+		assert_eq!(server.steam3().to_string(), "[A:1:1234:5]");
+		assert_eq!(server.steam3().to_string().parse(), Ok(server));
+		assert!(server.is_game_server_account() && server.is_anon_account());
+
+		let lobby = SteamId::new(99, CHAT_INSTANCE_FLAG_LOBBY, AccountType::Chat, Universe::Public);
+		assert_eq!(lobby.steam3().to_string(), "[L:1:99]");
+		assert_eq!("[L:1:99]".parse(), Ok(lobby));
+		assert!(lobby.is_lobby());
+
+		for bad in ["", "STEAM_0:2:1", "STEAM_0:0", "[X:1:2]", "[U:1:2:3:4]", "BOT", "STEAM_ID_LAN"] {
+			assert_eq!(bad.parse::<SteamId>(), Err(ParseSteamIdError), "{bad}");
+		}
+		assert!(!SteamId::individual(0, Universe::Public).is_valid());
+	}
+
+	#[test]
+	fn steam3_round_trip() {
+		for raw in 0.. {
+			let Some(account_type) = AccountType::from_raw(raw) else { break };
+			for instance in [0, 1, 3] {
+				let id = SteamId::new(1234, instance, account_type, Universe::Public);
+				let s = id.steam3().to_string();
+				assert_eq!(s.parse(), Ok(id), "{s}");
+			}
+		}
+	}
 }
@@ -1,5 +1,8 @@
-use ::core::ffi::{
-	c_float, c_int, c_short, c_ushort,
+use ::core::{
+	ffi::{
+		c_float, c_int, c_short,
+	},
+	ptr::NonNull,
 };
 use ::rse_math::{
 	Vector, Plane,
@@ -7,11 +10,10 @@ use ::rse_math::{
 
 use super::{
 	entities::BaseEntity,
+	Contents, DispSurfFlags, SurfFlags,
 	Surface,
 };
 
-// TODO: `DISPSURF_FLAG_*` as a bitset.
-
 /// Structure that is returned when a box is swept through the world.
 #[derive(Debug)]
 #[repr(C)]
@@ -27,6 +29,39 @@ pub struct GameTrace {
 	pub hitbox: c_int,
 }
 
+impl GameTrace {
+	/// Returns `true` if the trace hit something, or started in a solid.
+	pub const fn did_hit(&self) -> bool {
+		self.base.did_hit()
+	}
+
+	/// Returns `true` if the trace started in a solid.
+	pub const fn start_solid(&self) -> bool {
+		self.base.started_in_solid
+	}
+
+	/// Returns the entity that the trace hit.
+	pub const fn entity(&self) -> Option<NonNull<BaseEntity>> {
+		NonNull::new(self.entity)
+	}
+
+	/// Returns `true` if the trace hit `world`, which must be the world entity (entity index `0`).
+	pub fn hit_world(&self, world: *const BaseEntity) -> bool {
+		!self.entity.is_null() && self.entity.cast_const() == world
+	}
+
+	/// Returns `true` if the trace hit an entity other than `world`,
+	/// which must be the world entity (entity index `0`).
+	pub fn hit_non_world_entity(&self, world: *const BaseEntity) -> bool {
+		!self.entity.is_null() && self.entity.cast_const() != world
+	}
+
+	/// Returns `true` if the trace hit a sky surface.
+	pub const fn hit_sky(&self) -> bool {
+		self.did_hit() && self.surface.flags.intersects(SurfFlags::SKY.union(SurfFlags::SKY2D))
+	}
+}
+
 // `trace_t` is aliased to `CGameTrace`.
 pub type Trace = GameTrace;
 
@@ -38,8 +73,25 @@ pub struct BaseTrace {
 	pub end_pos: Vector,
 	pub plane: Plane,
 	pub fraction: c_float,
-	pub contents: c_int,
-	pub disp_flags: c_ushort,
+	pub contents: Contents,
+	pub disp_flags: DispSurfFlags,
 	pub all_solid: bool,
 	pub started_in_solid: bool,
 }
+
+impl BaseTrace {
+	/// Returns `true` if the trace hit something, or started in a solid.
+	pub const fn did_hit(&self) -> bool {
+		self.fraction < 1.0 || self.all_solid || self.started_in_solid
+	}
+
+	/// Returns `true` if the trace started in a solid.
+	pub const fn start_solid(&self) -> bool {
+		self.started_in_solid
+	}
+
+	/// Returns `true` if the whole trace was in a solid.
+	pub const fn all_solid(&self) -> bool {
+		self.all_solid
+	}
+}
@@ -0,0 +1,414 @@
+//! Parser for the text format of the entity lump of BSP files,
+//! as returned by `IVEngineServer::GetMapEntitiesString`.
+
+use ::alloc::{
+	ffi::CString,
+	vec::Vec,
+};
+use ::core::ffi::{
+	CStr, c_float, c_int,
+};
+use ::rse_math::{
+	QAngle, Vector,
+};
+
+/// Separator of the fields of output connections in newer versions of the engine.
+pub const OUTPUT_SEPARATOR: u8 = 0x1b;
+
+/// Error that occured while parsing an entity lump.
+#[derive(Debug, Clone, PartialEq, Eq, ::thiserror::Error)]
+#[error("line {line}: {kind}")]
+pub struct ParseError {
+	/// Line number, starting from `1`, in the lump where the error occured.
+	pub line: usize,
+	pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ::thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error("unexpected end of lump")]
+	UnexpectedEof,
+	#[error("unterminated quoted string")]
+	UnterminatedString,
+	#[error("expected `{{` at the start of an entity")]
+	ExpectedOpenBrace,
+	#[error("expected key name or `}}`")]
+	ExpectedKey,
+	#[error("expected value after key name")]
+	ExpectedValue,
+}
+
+/// List of entities parsed from an entity lump.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EntityLump {
+	entities: Vec<Entity>,
+}
+
+impl EntityLump {
+	/// Parses the text of an entity lump.
+	///
+	/// Parsing stops at the first nul byte, if any.
+	pub fn parse(text: &[u8]) -> Result<Self, ParseError> {
+		let text = match text.iter().position(move |&b| b == 0) {
+			Some(end) => &text[..end],
+			None => text,
+		};
+		let mut tokens = Tokenizer::new(text);
+		let mut entities = Vec::new();
+		while let Some(token) = tokens.next()? {
+			if token != Token::OpenBrace {
+				return Err(tokens.error(ParseErrorKind::ExpectedOpenBrace))
+			}
+			let mut pairs = Vec::new();
+			loop {
+				let key = match tokens.next()? {
+					Some(Token::CloseBrace) => break,
+					Some(Token::String(key)) => key,
+					Some(Token::OpenBrace) => return Err(tokens.error(ParseErrorKind::ExpectedKey)),
+					None => return Err(tokens.error(ParseErrorKind::UnexpectedEof)),
+				};
+				let value = match tokens.next()? {
+					Some(Token::String(value)) => value,
+					Some(_) => return Err(tokens.error(ParseErrorKind::ExpectedValue)),
+					None => return Err(tokens.error(ParseErrorKind::UnexpectedEof)),
+				};
+				pairs.push((to_c_string(key), to_c_string(value)));
+			}
+			entities.push(Entity {
+				pairs,
+			});
+		}
+		Ok(Self {
+			entities,
+		})
+	}
+
+	pub fn entities(&self) -> &[Entity] {
+		&self.entities
+	}
+	pub fn len(&self) -> usize {
+		self.entities.len()
+	}
+	pub fn is_empty(&self) -> bool {
+		self.entities.is_empty()
+	}
+	pub fn iter(&self) -> ::core::slice::Iter<'_, Entity> {
+		self.entities.iter()
+	}
+
+	/// Returns the `worldspawn` entity, which is the first entity of the lump.
+	pub fn world(&self) -> Option<&Entity> {
+		self.entities.first().filter(move |entity| entity.classname().is_some_and(move |c| eq_ignore_case(c, c"worldspawn")))
+	}
+
+	/// Returns an iterator over the entities with the given `classname`, compared case-insensitively.
+	pub fn by_classname<'a>(&'a self, classname: &'a CStr) -> impl Iterator<Item = &'a Entity> + 'a {
+		self.entities.iter().filter(move |entity| entity.classname().is_some_and(move |c| eq_ignore_case(c, classname)))
+	}
+
+	/// Returns an iterator over the entities with the given `targetname`, compared case-insensitively.
+	pub fn by_targetname<'a>(&'a self, targetname: &'a CStr) -> impl Iterator<Item = &'a Entity> + 'a {
+		self.entities.iter().filter(move |entity| entity.targetname().is_some_and(move |t| eq_ignore_case(t, targetname)))
+	}
+}
+
+impl<'a> IntoIterator for &'a EntityLump {
+	type Item = &'a Entity;
+	type IntoIter = ::core::slice::Iter<'a, Entity>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl IntoIterator for EntityLump {
+	type Item = Entity;
+	type IntoIter = ::alloc::vec::IntoIter<Entity>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.entities.into_iter()
+	}
+}
+
+/// Entity of an entity lump, which is a list of key-value pairs.
+///
+/// Keys may appear multiple times, which is how entities have multiple output connections.
+/// Like in the engine, key names are compared case-insensitively.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Entity {
+	pairs: Vec<(CString, CString)>,
+}
+
+impl Entity {
+	/// Returns all key-value pairs of the entity, in the order they appear in the lump.
+	pub fn pairs(&self) -> impl Iterator<Item = (&CStr, &CStr)> {
+		self.pairs.iter().map(move |(key, value)| (key.as_c_str(), value.as_c_str()))
+	}
+
+	/// Returns the value of the first key named `key`.
+	pub fn get(&self, key: &CStr) -> Option<&CStr> {
+		self.get_all(key).next()
+	}
+
+	/// Returns the values of all keys named `key`.
+	pub fn get_all<'a>(&'a self, key: &CStr) -> impl Iterator<Item = &'a CStr> {
+		self.pairs.iter()
+			.filter(move |(k, _)| eq_ignore_case(k, key))
+			.map(move |(_, value)| value.as_c_str())
+	}
+
+	/// Returns the value of `key` parsed as a float.
+	pub fn get_float(&self, key: &CStr) -> Option<c_float> {
+		parse_number(self.get(key)?.to_bytes())
+	}
+
+	/// Returns the value of `key` parsed as an integer.
+	pub fn get_int(&self, key: &CStr) -> Option<c_int> {
+		parse_number(self.get(key)?.to_bytes())
+	}
+
+	/// Returns the value of `key` parsed as three space-separated floats.
+	pub fn get_vector(&self, key: &CStr) -> Option<[c_float; 3]> {
+		parse_floats(self.get(key)?)
+	}
+
+	pub fn classname(&self) -> Option<&CStr> {
+		self.get(c"classname")
+	}
+	pub fn targetname(&self) -> Option<&CStr> {
+		self.get(c"targetname")
+	}
+
+	pub fn origin(&self) -> Option<Vector> {
+		self.get_vector(c"origin").map(move |[x, y, z]| Vector::new(x, y, z))
+	}
+
+	/// Returns the `angles` of the entity,
+	/// or the yaw given by the `angle` key if there is no `angles` key.
+	pub fn angles(&self) -> Option<QAngle> {
+		match self.get_vector(c"angles") {
+			Some([x, y, z]) => Some(QAngle::new(x, y, z)),
+			None => self.get_float(c"angle").map(move |yaw| QAngle::new(0.0, yaw, 0.0)),
+		}
+	}
+
+	/// Returns an iterator over the output connections of the entity.
+	///
+	/// Any key whose value has the format of an output connection is considered to be an output.
+	pub fn outputs(&self) -> impl Iterator<Item = Output<'_>> {
+		self.pairs.iter().filter_map(move |(key, value)| Output::parse(key, value))
+	}
+}
+
+/// Output connection of an entity, which fires `input` on the entities named `target` when `output` is fired.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Output<'a> {
+	pub output: &'a CStr,
+	pub target: &'a [u8],
+	pub input: &'a [u8],
+	pub parameter: &'a [u8],
+	/// Delay, in seconds, before firing the input.
+	pub delay: c_float,
+	/// Number of times that the connection fires, or `-1` to fire indefinitely.
+	pub times_to_fire: c_int,
+}
+
+impl<'a> Output<'a> {
+	/// Parses an output connection from the key `output` and its value,
+	/// which has the format `target,input,parameter,delay,times_to_fire`.
+	///
+	/// The fields may instead be separated by [`OUTPUT_SEPARATOR`], in which case commas are allowed in them.
+	pub fn parse(output: &'a CStr, value: &'a CStr) -> Option<Self> {
+		let value = value.to_bytes();
+		let separator = if value.contains(&OUTPUT_SEPARATOR) { OUTPUT_SEPARATOR } else { b',' };
+		let mut fields = value.split(move |&b| b == separator);
+		let (Some(target), Some(input), Some(parameter), Some(delay), Some(times_to_fire), None) = (
+			fields.next(), fields.next(), fields.next(), fields.next(), fields.next(), fields.next(),
+		) else {
+			return None
+		};
+		Some(Self {
+			output,
+			target,
+			input,
+			parameter,
+			delay: parse_number(delay)?,
+			times_to_fire: parse_number(times_to_fire)?,
+		})
+	}
+
+	/// Returns `true` if the connection fires indefinitely.
+	pub const fn is_infinite(&self) -> bool {
+		self.times_to_fire < 0
+	}
+}
+
+fn eq_ignore_case(a: &CStr, b: &CStr) -> bool {
+	a.to_bytes().eq_ignore_ascii_case(b.to_bytes())
+}
+
+fn to_c_string(bytes: &[u8]) -> CString {
+	// SAFETY: The lump was truncated at the first nul byte.
+	unsafe { CString::from_vec_unchecked(bytes.into()) }
+}
+
+fn parse_number<T: ::core::str::FromStr>(s: &[u8]) -> Option<T> {
+	::core::str::from_utf8(s).ok()?.trim().parse().ok()
+}
+
+fn parse_floats(s: &CStr) -> Option<[c_float; 3]> {
+	let mut parts = s.to_bytes().split(u8::is_ascii_whitespace).filter(move |part| !part.is_empty());
+	let floats = [
+		parse_number(parts.next()?)?,
+		parse_number(parts.next()?)?,
+		parse_number(parts.next()?)?,
+	];
+	parts.next().is_none().then_some(floats)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+	OpenBrace,
+	CloseBrace,
+	String(&'a [u8]),
+}
+
+struct Tokenizer<'a> {
+	text: &'a [u8],
+	line: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+	const fn new(text: &'a [u8]) -> Self {
+		Self {
+			text,
+			line: 1,
+		}
+	}
+
+	const fn error(&self, kind: ParseErrorKind) -> ParseError {
+		ParseError {
+			line: self.line,
+			kind,
+		}
+	}
+
+	fn advance(&mut self, n: usize) -> &'a [u8] {
+		let (skipped, rest) = self.text.split_at(n);
+		self.line += skipped.iter().filter(move |&&b| b == b'\n').count();
+		self.text = rest;
+		skipped
+	}
+
+	fn skip_whitespace(&mut self) {
+		loop {
+			let n = self.text.iter().position(move |b| !b.is_ascii_whitespace()).unwrap_or(self.text.len());
+			self.advance(n);
+			if !self.text.starts_with(b"//") {
+				break
+			}
+			let n = self.text.iter().position(move |&b| b == b'\n').unwrap_or(self.text.len());
+			self.advance(n);
+		}
+	}
+
+	fn next(&mut self) -> Result<Option<Token<'a>>, ParseError> {
+		self.skip_whitespace();
+		let Some(&first) = self.text.first() else { return Ok(None) };
+		let token = match first {
+			b'{' => {
+				self.advance(1);
+				Token::OpenBrace
+			}
+			b'}' => {
+				self.advance(1);
+				Token::CloseBrace
+			}
+			b'"' => {
+				let Some(len) = self.text[1..].iter().position(move |&b| b == b'"') else {
+					return Err(self.error(ParseErrorKind::UnterminatedString))
+				};
+				self.advance(1);
+				let string = self.advance(len);
+				self.advance(1);
+				Token::String(string)
+			}
+			_ => {
+				let len = self.text.iter()
+					.position(move |&b| b.is_ascii_whitespace() || matches!(b, b'{' | b'}' | b'"'))
+					.unwrap_or(self.text.len());
+				Token::String(self.advance(len))
+			}
+		};
+		Ok(Some(token))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const LUMP: &[u8] = b"{\n\
+		\"world_maxs\" \"1024 1024 512\"\n\
+		\"classname\" \"worldspawn\"\n\
+		}\n\
+		{\n\
+		\"origin\" \"-128 64.5 16\"\n\
+		\"angles\" \"0 90 0\"\n\
+		\"classname\" \"info_player_start\"\n\
+		}\n\
+		{\n\
+		\"targetname\" \"relay\"\n\
+		\"classname\" \"logic_relay\"\n\
+		\"OnTrigger\" \"door,Open,,0.5,-1\"\n\
+		\"OnTrigger\" \"counter\x1bAdd\x1b1,2\x1b0\x1b1\"\n\
+		\"angle\" \"45\"\n\
+		}\n\0";
+
+	#[test]
+	fn parse() {
+		let lump = EntityLump::parse(LUMP).unwrap();
+		assert_eq!(lump.len(), 3);
+		assert_eq!(lump.world().unwrap().get(c"WORLD_MAXS"), Some(c"1024 1024 512"));
+
+		let start = lump.by_classname(c"INFO_PLAYER_START").next().unwrap();
+		assert_eq!(start.origin(), Some(Vector::new(-128.0, 64.5, 16.0)));
+		assert_eq!(start.angles(), Some(QAngle::new(0.0, 90.0, 0.0)));
+		assert_eq!(start.outputs().count(), 0);
+
+		let relay = lump.by_targetname(c"relay").next().unwrap();
+		assert_eq!(relay.classname(), Some(c"logic_relay"));
+		assert_eq!(relay.angles(), Some(QAngle::new(0.0, 45.0, 0.0)));
+		assert_eq!(relay.origin(), None);
+		assert_eq!(relay.get_all(c"ontrigger").count(), 2);
+
+		let outputs: Vec<_> = relay.outputs().collect();
+		assert_eq!(outputs, [
+			Output {
+				output: c"OnTrigger",
+				target: b"door",
+				input: b"Open",
+				parameter: b"",
+				delay: 0.5,
+				times_to_fire: -1,
+			},
+			Output {
+				output: c"OnTrigger",
+				target: b"counter",
+				input: b"Add",
+				parameter: b"1,2",
+				delay: 0.0,
+				times_to_fire: 1,
+			},
+		]);
+		assert!(outputs[0].is_infinite());
+	}
+
+	#[test]
+	fn errors() {
+		let error = |text: &[u8]| EntityLump::parse(text).unwrap_err();
+		assert_eq!(error(b"{ \"a\" }"), ParseError { line: 1, kind: ParseErrorKind::ExpectedValue });
+		assert_eq!(error(b"{\n\"a\" \"b\"\n"), ParseError { line: 3, kind: ParseErrorKind::UnexpectedEof });
+		assert_eq!(error(b"\"a\" \"b\""), ParseError { line: 1, kind: ParseErrorKind::ExpectedOpenBrace });
+		assert_eq!(error(b"{ \"a"), ParseError { line: 1, kind: ParseErrorKind::UnterminatedString });
+		assert!(EntityLump::parse(b"// Comment.\n{ classname worldspawn }").is_ok());
+	}
+}
@@ -0,0 +1,179 @@
+//! Binary format of [`KeyValues`], as used for network transfer and cache files.
+//!
+//! Each key is stored as a type tag byte, a nul-terminated name and the value,
+//! where keys with subkeys are followed by their subkeys.
+//! Each list of keys ends with a [`KeyValuesType::NumTypes`] tag.
+//! All numbers are little-endian.
+
+use ::alloc::{
+	ffi::CString,
+	string::String,
+	vec::Vec,
+};
+use ::core::ffi::{
+	CStr, c_void,
+};
+use ::rse_math::Color;
+
+use crate::cppdef::KeyValuesType;
+
+use super::{
+	KeyValues, Value,
+};
+
+/// Maximum depth of nested subkeys that can be read, like in the engine.
+pub const MAX_BINARY_DEPTH: usize = 100;
+
+/// Error that occured while reading the binary format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ::thiserror::Error)]
+pub enum BinaryError {
+	#[error("unexpected end of data")]
+	UnexpectedEof,
+	#[error("unknown type tag {0}")]
+	UnknownType(u8),
+	#[error("subkeys are nested too deeply")]
+	TooDeep,
+}
+
+impl KeyValues {
+	/// Reads a list of keys in the binary format.
+	///
+	/// Like in the engine, the list may also end at the end of `data` instead of with an end tag.
+	pub fn parse_binary(data: &[u8]) -> Result<Vec<KeyValues>, BinaryError> {
+		let mut reader = Reader {
+			data,
+		};
+		reader.list(0)
+	}
+
+	/// Returns the binary format of this key as a list of one key.
+	///
+	/// See also [`write_binary`](Self::write_binary).
+	pub fn to_binary(&self) -> Vec<u8> {
+		let mut out = Vec::new();
+		self.write_binary(&mut out);
+		out
+	}
+
+	/// Appends the binary format of this key as a list of one key to `out`.
+	pub fn write_binary(&self, out: &mut Vec<u8>) {
+		Self::write_binary_list(::core::slice::from_ref(self), out)
+	}
+
+	/// Appends the binary format of a list of keys to `out`.
+	///
+	/// Wide strings are written as a 16-bit length followed by that many UTF-16 code units.
+	/// Pointers are truncated to 32 bits, like in the engine.
+	pub fn write_binary_list(keys: &[KeyValues], out: &mut Vec<u8>) {
+		for kv in keys {
+			out.push(kv.data_type() as u8);
+			out.extend_from_slice(kv.name().to_bytes_with_nul());
+			match *kv.value() {
+				Value::Subkeys(ref subkeys) => Self::write_binary_list(subkeys, out),
+				Value::String(ref s) => out.extend_from_slice(s.to_bytes_with_nul()),
+				Value::Int(i) => out.extend_from_slice(&i.to_le_bytes()),
+				Value::Float(f) => out.extend_from_slice(&f.to_le_bytes()),
+				Value::Ptr(p) => out.extend_from_slice(&(p as usize as u32).to_le_bytes()),
+				Value::WString(ref s) => {
+					let len = s.encode_utf16().count().min(i16::MAX as usize);
+					out.extend_from_slice(&(len as i16).to_le_bytes());
+					for unit in s.encode_utf16().take(len) {
+						out.extend_from_slice(&unit.to_le_bytes());
+					}
+				}
+				Value::Color(Color { rgba }) => out.extend_from_slice(&rgba),
+				Value::Uint64(u) => out.extend_from_slice(&u.to_le_bytes()),
+			}
+		}
+		out.push(KeyValuesType::NumTypes as u8);
+	}
+}
+
+struct Reader<'a> {
+	data: &'a [u8],
+}
+
+impl Reader<'_> {
+	fn bytes<const N: usize>(&mut self) -> Result<[u8; N], BinaryError> {
+		let (bytes, rest) = self.data.split_first_chunk().ok_or(BinaryError::UnexpectedEof)?;
+		self.data = rest;
+		Ok(*bytes)
+	}
+
+	fn c_string(&mut self) -> Result<CString, BinaryError> {
+		let s = CStr::from_bytes_until_nul(self.data).map_err(move |_| BinaryError::UnexpectedEof)?;
+		self.data = &self.data[s.count_bytes() + 1..];
+		Ok(s.into())
+	}
+
+	fn list(&mut self, depth: usize) -> Result<Vec<KeyValues>, BinaryError> {
+		if depth > MAX_BINARY_DEPTH {
+			return Err(BinaryError::TooDeep)
+		}
+
+		let mut keys = Vec::new();
+		loop {
+			let [tag] = self.bytes()?;
+			let data_type = KeyValuesType::from_raw(tag).ok_or(BinaryError::UnknownType(tag))?;
+			if data_type == KeyValuesType::NumTypes {
+				break
+			}
+
+			let name = self.c_string()?;
+			let value = match data_type {
+				KeyValuesType::None => Value::Subkeys(self.list(depth + 1)?),
+				KeyValuesType::String => Value::String(self.c_string()?),
+				KeyValuesType::Int => Value::Int(i32::from_le_bytes(self.bytes()?)),
+				KeyValuesType::Float => Value::Float(f32::from_le_bytes(self.bytes()?)),
+				KeyValuesType::Ptr => Value::Ptr(u32::from_le_bytes(self.bytes()?) as usize as *mut c_void),
+				KeyValuesType::WString => {
+					let len = i16::from_le_bytes(self.bytes()?).max(0) as usize;
+					let mut units = Vec::with_capacity(len);
+					for _ in 0..len {
+						units.push(u16::from_le_bytes(self.bytes()?));
+					}
+					Value::WString(String::from_utf16_lossy(&units))
+				}
+				KeyValuesType::Color => Value::Color(Color {
+					rgba: self.bytes()?,
+				}),
+				KeyValuesType::Uint64 => Value::Uint64(u64::from_le_bytes(self.bytes()?)),
+				KeyValuesType::NumTypes => unreachable!(),
+			};
+			keys.push(KeyValues::with_value(name, value));
+
+			if self.data.is_empty() {
+				break
+			}
+		}
+		Ok(keys)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn round_trip() {
+		let kv = KeyValues::new(c"cmd")
+			.with(c"name", c"menu")
+			.with(c"int", -5)
+			.with(c"float", 0.25f32)
+			.with(c"ptr", 0x1234 as *mut c_void)
+			.with(c"color", Color::rgba(1, 2, 3, 4))
+			.with(c"id", 76561197960287930u64)
+			.with(c"nested/empty", Vec::new())
+			.with_subkey(KeyValues::with_value(c"wide", Value::WString("héllo".into())));
+		let data = kv.to_binary();
+		assert_eq!(KeyValues::parse_binary(&data), Ok(::alloc::vec![kv]));
+	}
+
+	#[test]
+	fn layout() {
+		let kv = KeyValues::new(c"a").with(c"b", 1).with(c"c", c"d");
+		assert_eq!(kv.to_binary(), b"\x00a\0\x02b\0\x01\0\0\0\x01c\0d\0\x08\x08");
+		assert_eq!(KeyValues::parse_binary(b"\x02b\0\x01\0\0"), Err(BinaryError::UnexpectedEof));
+		assert_eq!(KeyValues::parse_binary(b"\x09"), Err(BinaryError::UnknownType(9)));
+	}
+}
@@ -0,0 +1,264 @@
+use ::alloc::{
+	ffi::CString,
+	string::String,
+	vec::Vec,
+};
+use ::core::{
+	alloc::Layout,
+	ffi::{
+		CStr, c_char, c_int,
+	},
+	fmt,
+	mem::forget,
+	ptr::{
+		NonNull, null_mut,
+	},
+};
+use ::rse_cpp::{
+	AsObject, VtObject, virtual_call,
+};
+use ::rse_math::Color;
+use ::rse_tier0::{
+	linked::mem::LinkedTier0Allocator,
+	Tier0Allocator,
+};
+
+use crate::{
+	cppdef::{
+		KeyValues as CKeyValues, KeyValuesData, KeyValuesType,
+		KeyValuesSystemVt, HKeySymbol,
+		wchar_t,
+	},
+	WideCStr, encode_wide,
+};
+
+use super::{
+	KeyValues, Value,
+};
+
+/// Safe interface to `IKeyValuesSystem`,
+/// used for converting between [`KeyValues`] trees and their C++ counterparts.
+pub trait KeyValuesSystemImpl: AsObject<KeyValuesSystemVt> {
+	/// Returns the symbol for the key name `name`,
+	/// creating it if it doesn't exist and `create` is `true`.
+	fn symbol_for_string(&self, name: &CStr, create: bool) -> HKeySymbol {
+		unsafe { virtual_call!(self.as_object() => get_symbol_for_string(name.as_ptr(), create)) }
+	}
+
+	/// Returns the key name for `symbol`.
+	fn string_for_symbol(&self, symbol: HKeySymbol) -> Option<&CStr> {
+		unsafe {
+			let ptr = virtual_call!(self.as_object() => get_string_for_symbol(symbol));
+			(!ptr.is_null()).then(move || CStr::from_ptr(ptr))
+		}
+	}
+
+	/// Returns a C++ copy of `kv` that can be passed to the engine.
+	fn to_engine(&self, kv: &KeyValues) -> EngineKeyValues<'_> {
+		let system = self.as_object();
+		EngineKeyValues {
+			system,
+			root: unsafe { new_node(system, kv) },
+		}
+	}
+
+	/// Returns an owned copy of the C++ tree pointed to by `kv`.
+	///
+	/// Peers of `kv` are not copied.
+	///
+	/// # Safety
+	/// `kv` must point to a valid C++ `KeyValues` whose symbols were created by this system.
+	unsafe fn read_engine(&self, kv: *const CKeyValues) -> KeyValues {
+		unsafe { read_node(self.as_object(), kv) }
+	}
+}
+impl<T: ?Sized + AsObject<KeyValuesSystemVt>> KeyValuesSystemImpl for T {}
+
+/// Owned C++ `KeyValues` tree,
+/// freed when dropped unless ownership is given to the engine with [`into_raw`](Self::into_raw).
+pub struct EngineKeyValues<'a> {
+	system: &'a VtObject<KeyValuesSystemVt>,
+	root: NonNull<CKeyValues>,
+}
+
+impl fmt::Debug for EngineKeyValues<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("EngineKeyValues").field(&self.root).finish()
+	}
+}
+
+impl<'a> EngineKeyValues<'a> {
+	/// Returns an [`EngineKeyValues`] that owns the C++ tree pointed to by `root`.
+	///
+	/// # Safety
+	/// `root` must point to a valid C++ `KeyValues` with no peers,
+	/// whose memory was allocated by `system`,
+	/// and which isn't owned by anything else.
+	pub const unsafe fn from_raw(system: &'a VtObject<KeyValuesSystemVt>, root: NonNull<CKeyValues>) -> Self {
+		Self {
+			system,
+			root,
+		}
+	}
+
+	/// Consumes the tree, returning a pointer to it without freeing it.
+	///
+	/// This is used for functions like `IVEngineServer::ClientCommandKeyValues`,
+	/// which take ownership of the `KeyValues` passed to them.
+	pub const fn into_raw(self) -> NonNull<CKeyValues> {
+		let root = self.root;
+		forget(self);
+		root
+	}
+
+	pub const fn as_ptr(&self) -> *const CKeyValues {
+		self.root.as_ptr()
+	}
+
+	pub const fn as_mut_ptr(&mut self) -> *mut CKeyValues {
+		self.root.as_ptr()
+	}
+
+	/// Returns an owned copy of the tree.
+	pub fn to_key_values(&self) -> KeyValues {
+		unsafe { read_node(self.system, self.root.as_ptr()) }
+	}
+}
+
+impl Drop for EngineKeyValues<'_> {
+	fn drop(&mut self) {
+		unsafe { free_node(self.system, self.root.as_ptr()) }
+	}
+}
+
+unsafe fn alloc_array<T: Copy>(items: impl ExactSizeIterator<Item = T>, terminator: T) -> *mut T {
+	let layout = Layout::array::<T>(items.len() + 1).unwrap();
+	unsafe {
+		let ptr = LinkedTier0Allocator.alloc(layout.size()) as *mut T;
+		if ptr.is_null() {
+			::alloc::alloc::handle_alloc_error(layout)
+		}
+		let mut end = ptr;
+		for item in items {
+			end.write(item);
+			end = end.add(1);
+		}
+		end.write(terminator);
+		ptr
+	}
+}
+
+unsafe fn new_node(system: &VtObject<KeyValuesSystemVt>, kv: &KeyValues) -> NonNull<CKeyValues> {
+	let layout = Layout::new::<CKeyValues>();
+	unsafe {
+		let ptr = virtual_call!(system => alloc_key_values_memory(layout.size() as c_int)) as *mut CKeyValues;
+		let Some(node) = NonNull::new(ptr) else {
+			::alloc::alloc::handle_alloc_error(layout)
+		};
+		node.write(CKeyValues {
+			key_name: virtual_call!(system => get_symbol_for_string(kv.name().as_ptr(), true)),
+			string_value: null_mut(),
+			wstring_value: null_mut(),
+			data: KeyValuesData {
+				ptr_value: null_mut(),
+			},
+			data_type: kv.data_type() as c_char,
+			has_escape_sequences: false,
+			evaluate_conditionals: true,
+			unused: [0],
+			peer: null_mut(),
+			sub: null_mut(),
+			chain: null_mut(),
+		});
+
+		let c = &mut *node.as_ptr();
+		match *kv.value() {
+			Value::Subkeys(ref subkeys) => {
+				let mut tail = &mut c.sub;
+				for subkey in subkeys {
+					let sub = new_node(system, subkey).as_ptr();
+					*tail = sub;
+					tail = &mut (*sub).peer;
+				}
+			}
+			Value::String(ref s) => {
+				let bytes = s.to_bytes();
+				c.string_value = alloc_array(bytes.iter().map(move |&b| b as c_char), 0);
+			}
+			Value::Int(i) => c.data.int_value = i,
+			Value::Float(f) => c.data.float_value = f,
+			Value::Ptr(p) => c.data.ptr_value = p,
+			Value::WString(ref s) => {
+				let wide: Vec<wchar_t> = encode_wide(s).collect();
+				c.wstring_value = alloc_array(wide.into_iter(), 0);
+			}
+			Value::Color(Color { rgba }) => c.data.color = rgba,
+			Value::Uint64(u) => {
+				c.string_value = alloc_array(u.to_ne_bytes().into_iter().map(move |b| b as c_char), 0);
+			}
+		}
+		node
+	}
+}
+
+unsafe fn free_node(system: &VtObject<KeyValuesSystemVt>, node: *mut CKeyValues) {
+	unsafe {
+		let c = &mut *node;
+		let mut sub = c.sub;
+		while !sub.is_null() {
+			let next = (*sub).peer;
+			free_node(system, sub);
+			sub = next;
+		}
+		if !c.string_value.is_null() {
+			LinkedTier0Allocator.free(c.string_value as _);
+		}
+		if !c.wstring_value.is_null() {
+			LinkedTier0Allocator.free(c.wstring_value as _);
+		}
+		virtual_call!(system => free_key_values_memory(node as _));
+	}
+}
+
+unsafe fn read_wide(ptr: *const wchar_t) -> String {
+	unsafe { WideCStr::from_ptr(ptr).to_string_lossy() }
+}
+
+unsafe fn read_node(system: &VtObject<KeyValuesSystemVt>, node: *const CKeyValues) -> KeyValues {
+	unsafe {
+		let c = &*node;
+		let name = system.string_for_symbol(c.key_name).map(CString::from).unwrap_or_default();
+		// Unknown types are treated like nodes without a value.
+		let value = match c.data_type().unwrap_or(KeyValuesType::None) {
+			KeyValuesType::None | KeyValuesType::NumTypes => {
+				let mut subkeys = Vec::new();
+				let mut sub = c.sub as *const CKeyValues;
+				while !sub.is_null() {
+					subkeys.push(read_node(system, sub));
+					sub = (*sub).peer;
+				}
+				Value::Subkeys(subkeys)
+			}
+			KeyValuesType::String => Value::String(if !c.string_value.is_null() {
+				CStr::from_ptr(c.string_value).into()
+			} else {
+				CString::default()
+			}),
+			KeyValuesType::Int => Value::Int(c.data.int_value),
+			KeyValuesType::Float => Value::Float(c.data.float_value),
+			KeyValuesType::Ptr => Value::Ptr(c.data.ptr_value),
+			KeyValuesType::WString => Value::WString(if !c.wstring_value.is_null() {
+				read_wide(c.wstring_value)
+			} else {
+				String::new()
+			}),
+			KeyValuesType::Color => Value::Color(Color { rgba: c.data.color }),
+			KeyValuesType::Uint64 => Value::Uint64(if !c.string_value.is_null() {
+				(c.string_value as *const u64).read_unaligned()
+			} else {
+				0
+			}),
+		};
+		KeyValues::with_value(name, value)
+	}
+}
@@ -0,0 +1,258 @@
+//! Owned trees of key-value pairs, compatible with the engine's `KeyValues`.
+
+use ::alloc::{
+	ffi::CString,
+	vec::Vec,
+};
+use ::core::ffi::{
+	CStr, c_void,
+};
+use ::rse_math::Color;
+
+use crate::cppdef::KeyValuesType;
+
+mod binary;
+pub use binary::*;
+mod engine;
+pub use engine::*;
+mod text;
+pub use text::*;
+mod value;
+pub use value::*;
+
+/// Named node in a tree of key-value pairs.
+///
+/// Like in the engine, key names are compared case-insensitively,
+/// and paths of nested keys can be separated with `/`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct KeyValues {
+	name: CString,
+	value: Value,
+}
+
+impl KeyValues {
+	/// Returns a new node with no subkeys.
+	pub fn new(name: impl Into<CString>) -> Self {
+		Self {
+			name: name.into(),
+			value: Value::default(),
+		}
+	}
+
+	/// Returns a new node with the given value.
+	pub fn with_value(name: impl Into<CString>, value: impl Into<Value>) -> Self {
+		Self {
+			name: name.into(),
+			value: value.into(),
+		}
+	}
+
+	/// Returns `self` with the subkey at `path` set to `value`.
+	///
+	/// See also [`set`](Self::set).
+	pub fn with(mut self, path: &CStr, value: impl Into<Value>) -> Self {
+		self.set(path, value);
+		self
+	}
+
+	/// Returns `self` with `subkey` appended to its subkeys.
+	///
+	/// See also [`add_subkey`](Self::add_subkey).
+	pub fn with_subkey(mut self, subkey: KeyValues) -> Self {
+		self.add_subkey(subkey);
+		self
+	}
+
+	pub fn name(&self) -> &CStr {
+		&self.name
+	}
+
+	pub fn set_name(&mut self, name: impl Into<CString>) {
+		self.name = name.into();
+	}
+
+	pub const fn value(&self) -> &Value {
+		&self.value
+	}
+
+	pub const fn value_mut(&mut self) -> &mut Value {
+		&mut self.value
+	}
+
+	pub fn set_value(&mut self, value: impl Into<Value>) {
+		self.value = value.into();
+	}
+
+	pub fn into_value(self) -> Value {
+		self.value
+	}
+
+	pub const fn data_type(&self) -> KeyValuesType {
+		self.value.data_type()
+	}
+
+	/// Returns `true` if the node has subkeys rather than a value.
+	pub const fn has_subkeys(&self) -> bool {
+		matches!(self.value, Value::Subkeys(..))
+	}
+
+	/// Returns the subkeys of the node,
+	/// which is empty if the node holds a value.
+	pub fn subkeys(&self) -> &[KeyValues] {
+		self.value.as_subkeys().unwrap_or(&[])
+	}
+
+	/// Returns the subkeys of the node,
+	/// or `None` if the node holds a value.
+	pub fn subkeys_mut(&mut self) -> Option<&mut Vec<KeyValues>> {
+		match &mut self.value {
+			Value::Subkeys(subkeys) => Some(subkeys),
+			_ => None,
+		}
+	}
+
+	/// Returns the subkeys of the node,
+	/// replacing the value with an empty list of subkeys if it held a value.
+	fn subkeys_or_replace(&mut self) -> &mut Vec<KeyValues> {
+		if !self.has_subkeys() {
+			self.value = Value::default();
+		}
+		match &mut self.value {
+			Value::Subkeys(subkeys) => subkeys,
+			_ => unreachable!(),
+		}
+	}
+
+	/// Appends `subkey` to the subkeys of the node and returns a reference to it.
+	///
+	/// If the node held a value, it is replaced.
+	pub fn add_subkey(&mut self, subkey: KeyValues) -> &mut KeyValues {
+		let subkeys = self.subkeys_or_replace();
+		subkeys.push(subkey);
+		subkeys.last_mut().unwrap()
+	}
+
+	/// Removes the first direct subkey named `name` and returns it.
+	pub fn remove_subkey(&mut self, name: &CStr) -> Option<KeyValues> {
+		let subkeys = self.subkeys_mut()?;
+		let index = subkeys.iter().position(move |kv| names_eq(&kv.name, name.to_bytes()))?;
+		Some(subkeys.remove(index))
+	}
+
+	/// Returns the first subkey at the `/`-separated `path`.
+	pub fn find_key(&self, path: &CStr) -> Option<&KeyValues> {
+		path.to_bytes().split(move |&c| c == b'/')
+			.try_fold(self, move |kv, name| kv.subkeys().iter().find(move |kv| names_eq(&kv.name, name)))
+	}
+
+	/// Returns the first subkey at the `/`-separated `path`.
+	pub fn find_key_mut(&mut self, path: &CStr) -> Option<&mut KeyValues> {
+		path.to_bytes().split(move |&c| c == b'/')
+			.try_fold(self, move |kv, name| {
+				kv.subkeys_mut()?.iter_mut().find(move |kv| names_eq(&kv.name, name))
+			})
+	}
+
+	/// Returns the first subkey at the `/`-separated `path`,
+	/// creating it and any missing parents if they don't exist.
+	///
+	/// Nodes along the path that held values are turned into nodes with subkeys.
+	pub fn find_or_create_key(&mut self, path: &CStr) -> &mut KeyValues {
+		path.to_bytes().split(move |&c| c == b'/')
+			.fold(self, move |kv, name| {
+				let subkeys = kv.subkeys_or_replace();
+				match subkeys.iter().position(move |kv| names_eq(&kv.name, name)) {
+					Some(index) => &mut subkeys[index],
+					None => {
+						subkeys.push(KeyValues::new(CString::new(name).unwrap()));
+						subkeys.last_mut().unwrap()
+					}
+				}
+			})
+	}
+
+	/// Sets the value of the subkey at `path`, creating it if it doesn't exist.
+	pub fn set(&mut self, path: &CStr, value: impl Into<Value>) {
+		self.find_or_create_key(path).value = value.into();
+	}
+
+	/// Returns the value of the subkey at `path`.
+	pub fn get(&self, path: &CStr) -> Option<&Value> {
+		self.find_key(path).map(move |kv| &kv.value)
+	}
+
+	/// Returns the string value of the subkey at `path`,
+	/// or `None` if it doesn't exist or isn't a string.
+	pub fn get_string(&self, path: &CStr) -> Option<&CStr> {
+		self.get(path)?.as_c_str()
+	}
+
+	/// Returns the value of the subkey at `path` converted to an integer.
+	///
+	/// See also [`Value::to_int`].
+	pub fn get_int(&self, path: &CStr) -> Option<i32> {
+		self.get(path)?.to_int()
+	}
+
+	/// Returns the value of the subkey at `path` converted to an unsigned 64-bit integer.
+	///
+	/// See also [`Value::to_uint64`].
+	pub fn get_uint64(&self, path: &CStr) -> Option<u64> {
+		self.get(path)?.to_uint64()
+	}
+
+	/// Returns the value of the subkey at `path` converted to a float.
+	///
+	/// See also [`Value::to_float`].
+	pub fn get_float(&self, path: &CStr) -> Option<f32> {
+		self.get(path)?.to_float()
+	}
+
+	/// Returns the value of the subkey at `path` converted to a boolean.
+	///
+	/// See also [`Value::to_bool`].
+	pub fn get_bool(&self, path: &CStr) -> Option<bool> {
+		self.get(path)?.to_bool()
+	}
+
+	/// Returns the value of the subkey at `path` converted to a color.
+	///
+	/// See also [`Value::to_color`].
+	pub fn get_color(&self, path: &CStr) -> Option<Color> {
+		self.get(path)?.to_color()
+	}
+
+	/// Returns the pointer value of the subkey at `path`.
+	pub fn get_ptr(&self, path: &CStr) -> Option<*mut c_void> {
+		self.get(path)?.to_ptr()
+	}
+}
+
+fn names_eq(a: &CStr, b: &[u8]) -> bool {
+	a.to_bytes().eq_ignore_ascii_case(b)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn paths() {
+		let mut kv = KeyValues::new(c"root")
+			.with(c"title", c"Hello")
+			.with(c"level", 2)
+			.with(c"sub/color", Color::rgb(255, 0, 0));
+		assert_eq!(kv.get_string(c"TITLE"), Some(c"Hello"));
+		assert_eq!(kv.get_int(c"level"), Some(2));
+		assert_eq!(kv.get_color(c"Sub/Color"), Some(Color::rgb(255, 0, 0)));
+		assert_eq!(kv.get(c"sub/missing"), None);
+
+		kv.set(c"level/nested", 1.5f32);
+		assert!(kv.find_key(c"level").unwrap().has_subkeys());
+		assert_eq!(kv.get_float(c"level/nested"), Some(1.5));
+
+		let removed = kv.remove_subkey(c"sub").unwrap();
+		assert_eq!(removed.subkeys().len(), 1);
+		assert_eq!(kv.subkeys().len(), 2);
+	}
+}
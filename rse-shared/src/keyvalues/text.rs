@@ -0,0 +1,655 @@
+//! Text format of [`KeyValues`], also known as VDF.
+
+use ::alloc::{
+	ffi::CString,
+	format,
+	vec::Vec,
+};
+use ::core::ffi::CStr;
+
+use super::{
+	KeyValues, Value,
+	names_eq,
+};
+
+/// Maximum depth of nested `#include` and `#base` directives.
+pub const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Options for parsing the text format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+	/// Whether `\n`, `\t`, `\\`, `\"` and similar escape sequences are processed in quoted tokens.
+	///
+	/// The engine has this disabled by default, in which case `\"` is still unescaped.
+	pub escapes: bool,
+	/// Whether values are parsed into integers, floats and 64-bit integers like in the engine,
+	/// as opposed to always being kept as strings.
+	pub infer_types: bool,
+	/// Conditions that `[$NAME]` tags are evaluated against.
+	pub conditions: Conditions,
+}
+
+impl Default for ParseOptions {
+	fn default() -> Self {
+		Self {
+			escapes: false,
+			infer_types: false,
+			conditions: Conditions::host(),
+		}
+	}
+}
+
+/// Set of platform conditions for evaluating tags like `[$WIN32]` and `[!$X360&&$OSX]`.
+///
+/// Unknown conditions evaluate to `false`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conditions {
+	/// `$WIN32`, which is the engine's name for any desktop platform.
+	pub pc: bool,
+	/// `$WINDOWS`.
+	pub windows: bool,
+	/// `$LINUX`.
+	pub linux: bool,
+	/// `$OSX`.
+	pub osx: bool,
+	/// `$POSIX`.
+	pub posix: bool,
+	/// `$X360` and `$GAMECONSOLE`.
+	pub console: bool,
+}
+
+impl Conditions {
+	/// Returns the conditions for the platform that this crate was compiled for.
+	pub const fn host() -> Self {
+		Self {
+			pc: true,
+			windows: cfg!(windows),
+			linux: cfg!(target_os = "linux"),
+			osx: cfg!(target_os = "macos"),
+			posix: cfg!(unix),
+			console: false,
+		}
+	}
+
+	/// Returns `true` if the condition `name` (without the `$`) is defined.
+	pub fn is_defined(&self, name: &[u8]) -> bool {
+		let names: [(&[u8], bool); 7] = [
+			(b"WIN32", self.pc),
+			(b"WINDOWS", self.windows),
+			(b"LINUX", self.linux),
+			(b"OSX", self.osx),
+			(b"POSIX", self.posix),
+			(b"X360", self.console),
+			(b"GAMECONSOLE", self.console),
+		];
+		names.iter().any(move |&(n, value)| value && n.eq_ignore_ascii_case(name))
+	}
+
+	/// Evaluates a conditional tag such as `[$WIN32||$OSX]`, with or without the brackets.
+	///
+	/// `&&` binds tighter than `||`.
+	pub fn evaluate(&self, tag: &[u8]) -> bool {
+		let tag = tag.strip_prefix(b"[").unwrap_or(tag);
+		let tag = tag.strip_suffix(b"]").unwrap_or(tag);
+		split_bytes(tag, b"||").any(move |all| {
+			split_bytes(all, b"&&").all(move |term| {
+				let term = term.trim_ascii();
+				let (negate, term) = match term.strip_prefix(b"!") {
+					Some(term) => (true, term.trim_ascii()),
+					None => (false, term),
+				};
+				let name = term.strip_prefix(b"$").unwrap_or(term);
+				self.is_defined(name) != negate
+			})
+		})
+	}
+}
+
+fn split_bytes<'a>(mut s: &'a [u8], separator: &'static [u8]) -> impl Iterator<Item = &'a [u8]> {
+	let mut done = false;
+	::core::iter::from_fn(move || {
+		if done {
+			return None
+		}
+		match s.windows(separator.len()).position(move |w| w == separator) {
+			Some(i) => {
+				let part = &s[..i];
+				s = &s[i + separator.len()..];
+				Some(part)
+			}
+			None => {
+				done = true;
+				Some(s)
+			}
+		}
+	})
+}
+
+/// Error that occured while parsing the text format.
+#[derive(Debug, Clone, PartialEq, Eq, ::thiserror::Error)]
+#[error("line {line}: {kind}")]
+pub struct ParseError {
+	/// Line number, starting from `1`, in the file where the error occured.
+	pub line: usize,
+	pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ::thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error("unexpected end of file")]
+	UnexpectedEof,
+	#[error("unterminated quoted string")]
+	UnterminatedString,
+	#[error("expected key name")]
+	ExpectedKey,
+	#[error("expected `{{` after key name")]
+	ExpectedOpenBrace,
+	#[error("unexpected `}}`")]
+	UnexpectedCloseBrace,
+	#[error("conditional between key and value")]
+	MisplacedConditional,
+	#[error("expected file name after `#include` or `#base`")]
+	ExpectedIncludePath,
+	#[error("couldn't load included file {0:?}")]
+	IncludeNotFound(CString),
+	#[error("`#include` and `#base` directives are nested too deeply")]
+	IncludeTooDeep,
+	#[error("in included file {path:?}: {error}")]
+	InIncludedFile {
+		path: CString,
+		error: ::alloc::boxed::Box<ParseError>,
+	},
+}
+
+impl KeyValues {
+	/// Parses the text format into a list of top-level keys.
+	///
+	/// `#include` and `#base` directives result in an error.
+	/// See also [`parse_text_with`](Self::parse_text_with).
+	pub fn parse_text(text: &[u8], options: &ParseOptions) -> Result<Vec<KeyValues>, ParseError> {
+		Self::parse_text_with(text, options, move |_| None)
+	}
+
+	/// Parses the text format into a list of top-level keys,
+	/// calling `load` to get the contents of files named by `#include` and `#base` directives.
+	///
+	/// Keys from `#include`d files are appended to the list.
+	/// Keys from `#base` files are merged into top-level keys of the same name,
+	/// without overwriting any existing values.
+	pub fn parse_text_with(
+		text: &[u8], options: &ParseOptions,
+		mut load: impl FnMut(&CStr) -> Option<Vec<u8>>,
+	) -> Result<Vec<KeyValues>, ParseError> {
+		parse_document(text, options, &mut load, 0)
+	}
+
+	/// Returns the text format of this key, ending with a newline.
+	///
+	/// See also [`write_text`](Self::write_text).
+	pub fn to_text(&self, escapes: bool) -> Vec<u8> {
+		let mut out = Vec::new();
+		self.write_text(&mut out, escapes);
+		out
+	}
+
+	/// Appends the text format of this key to `out`, ending with a newline.
+	///
+	/// Keys are indented with tabs like in the engine.
+	/// If `escapes` is `true`, then backslashes, newlines and tabs are escaped, too.
+	/// Pointer values aren't written, and colors are written as space-separated components.
+	pub fn write_text(&self, out: &mut Vec<u8>, escapes: bool) {
+		write_key(self, out, escapes, 0)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+	Quoted,
+	Unquoted,
+	Open,
+	Close,
+	Conditional,
+}
+
+#[derive(Debug)]
+struct Token {
+	kind: TokenKind,
+	bytes: Vec<u8>,
+}
+
+impl Token {
+	fn is_directive(&self, name: &[u8]) -> bool {
+		self.kind == TokenKind::Unquoted && self.bytes.eq_ignore_ascii_case(name)
+	}
+}
+
+#[derive(Clone)]
+struct Tokenizer<'a> {
+	text: &'a [u8],
+	pos: usize,
+	line: usize,
+	escapes: bool,
+}
+
+impl<'a> Tokenizer<'a> {
+	const fn new(text: &'a [u8], escapes: bool) -> Self {
+		Self {
+			text,
+			pos: 0,
+			line: 1,
+			escapes,
+		}
+	}
+
+	const fn error(&self, kind: ParseErrorKind) -> ParseError {
+		ParseError {
+			line: self.line,
+			kind,
+		}
+	}
+
+	fn peek_byte(&self) -> Option<u8> {
+		self.text.get(self.pos).copied()
+	}
+
+	fn bump(&mut self) -> Option<u8> {
+		let c = self.peek_byte()?;
+		self.pos += 1;
+		if c == b'\n' {
+			self.line += 1;
+		}
+		Some(c)
+	}
+
+	fn skip_whitespace_and_comments(&mut self) {
+		while let Some(c) = self.peek_byte() {
+			if c.is_ascii_whitespace() {
+				self.bump();
+			} else if self.text[self.pos..].starts_with(b"//") {
+				while self.peek_byte().is_some_and(move |c| c != b'\n') {
+					self.bump();
+				}
+			} else {
+				break
+			}
+		}
+	}
+
+	fn next(&mut self) -> Result<Option<Token>, ParseError> {
+		self.skip_whitespace_and_comments();
+		let Some(c) = self.bump() else {
+			return Ok(None)
+		};
+		let (kind, bytes) = match c {
+			b'{' => (TokenKind::Open, Vec::new()),
+			b'}' => (TokenKind::Close, Vec::new()),
+			b'"' => (TokenKind::Quoted, self.quoted()?),
+			_ => {
+				let start = self.pos - 1;
+				while self.peek_byte().is_some_and(move |c| !c.is_ascii_whitespace() && !matches!(c, b'"' | b'{' | b'}')) {
+					self.bump();
+				}
+				let bytes = &self.text[start..self.pos];
+				let kind = if bytes.starts_with(b"[") && bytes.ends_with(b"]") {
+					TokenKind::Conditional
+				} else {
+					TokenKind::Unquoted
+				};
+				(kind, bytes.to_vec())
+			}
+		};
+		Ok(Some(Token {
+			kind,
+			bytes,
+		}))
+	}
+
+	fn quoted(&mut self) -> Result<Vec<u8>, ParseError> {
+		let mut bytes = Vec::new();
+		loop {
+			match self.bump() {
+				None => return Err(self.error(ParseErrorKind::UnterminatedString)),
+				Some(b'"') => return Ok(bytes),
+				Some(b'\\') if self.escapes => {
+					let Some(c) = self.bump() else {
+						return Err(self.error(ParseErrorKind::UnterminatedString))
+					};
+					match c {
+						b'n' => bytes.push(b'\n'),
+						b't' => bytes.push(b'\t'),
+						b'v' => bytes.push(0x0b),
+						b'b' => bytes.push(0x08),
+						b'r' => bytes.push(b'\r'),
+						b'f' => bytes.push(0x0c),
+						b'a' => bytes.push(0x07),
+						b'\\' | b'?' | b'\'' | b'"' => bytes.push(c),
+						_ => bytes.extend_from_slice(&[b'\\', c]),
+					}
+				}
+				Some(b'\\') if self.peek_byte() == Some(b'"') => {
+					self.bump();
+					bytes.push(b'"');
+				}
+				Some(c) => bytes.push(c),
+			}
+		}
+	}
+
+	fn expect_next(&mut self) -> Result<Token, ParseError> {
+		self.next()?.ok_or_else(|| self.error(ParseErrorKind::UnexpectedEof))
+	}
+}
+
+fn to_c_string(mut bytes: Vec<u8>) -> CString {
+	// Nul bytes can't appear in C strings, and the engine would stop reading the token at one anyway.
+	if let Some(nul) = bytes.iter().position(move |&c| c == 0) {
+		bytes.truncate(nul);
+	}
+	CString::new(bytes).unwrap()
+}
+
+fn parse_document(
+	text: &[u8], options: &ParseOptions,
+	load: &mut dyn FnMut(&CStr) -> Option<Vec<u8>>, depth: usize,
+) -> Result<Vec<KeyValues>, ParseError> {
+	let mut tokens = Tokenizer::new(text, options.escapes);
+	let mut keys = Vec::new();
+	let mut included = Vec::new();
+	let mut bases = Vec::new();
+
+	while let Some(token) = tokens.next()? {
+		let is_include = token.is_directive(b"#include");
+		if is_include || token.is_directive(b"#base") {
+			let path = match tokens.next()? {
+				Some(path) if matches!(path.kind, TokenKind::Quoted | TokenKind::Unquoted) => to_c_string(path.bytes),
+				_ => return Err(tokens.error(ParseErrorKind::ExpectedIncludePath)),
+			};
+			if depth >= MAX_INCLUDE_DEPTH {
+				return Err(tokens.error(ParseErrorKind::IncludeTooDeep))
+			}
+			let Some(sub_text) = load(&path) else {
+				return Err(tokens.error(ParseErrorKind::IncludeNotFound(path)))
+			};
+			let sub_keys = parse_document(&sub_text, options, load, depth + 1).map_err(|error| {
+				tokens.error(ParseErrorKind::InIncludedFile {
+					path,
+					error: error.into(),
+				})
+			})?;
+			if is_include { &mut included } else { &mut bases }.extend(sub_keys);
+			continue
+		}
+
+		if !matches!(token.kind, TokenKind::Quoted | TokenKind::Unquoted) {
+			return Err(tokens.error(match token.kind {
+				TokenKind::Close => ParseErrorKind::UnexpectedCloseBrace,
+				_ => ParseErrorKind::ExpectedKey,
+			}))
+		}
+
+		let mut accepted = true;
+		let mut open = tokens.expect_next()?;
+		if open.kind == TokenKind::Conditional {
+			accepted = options.conditions.evaluate(&open.bytes);
+			open = tokens.expect_next()?;
+		}
+		if open.kind != TokenKind::Open {
+			return Err(tokens.error(ParseErrorKind::ExpectedOpenBrace))
+		}
+
+		let subkeys = parse_block(&mut tokens, options)?;
+		if accepted {
+			keys.push(KeyValues::with_value(to_c_string(token.bytes), subkeys));
+		}
+	}
+
+	keys.extend(included);
+	for base in bases {
+		for key in keys.iter_mut().filter(|key| key.name().to_bytes().eq_ignore_ascii_case(base.name().to_bytes())) {
+			merge_missing(key, &base);
+		}
+	}
+	Ok(keys)
+}
+
+fn parse_block(tokens: &mut Tokenizer<'_>, options: &ParseOptions) -> Result<Vec<KeyValues>, ParseError> {
+	let mut keys = Vec::new();
+	loop {
+		let name = tokens.expect_next()?;
+		match name.kind {
+			TokenKind::Close => return Ok(keys),
+			TokenKind::Open => return Err(tokens.error(ParseErrorKind::ExpectedKey)),
+			_ => {}
+		}
+
+		let mut accepted = true;
+		let mut value = tokens.expect_next()?;
+		let mut was_conditional = false;
+		if value.kind == TokenKind::Conditional {
+			accepted = options.conditions.evaluate(&value.bytes);
+			value = tokens.expect_next()?;
+			was_conditional = true;
+		}
+
+		let value = match value.kind {
+			TokenKind::Close => return Err(tokens.error(ParseErrorKind::UnexpectedCloseBrace)),
+			TokenKind::Open => Value::Subkeys(parse_block(tokens, options)?),
+			_ if was_conditional => return Err(tokens.error(ParseErrorKind::MisplacedConditional)),
+			_ => {
+				// Look ahead for a conditional tag after the value.
+				let mut peek = tokens.clone();
+				if let Some(tag) = peek.next()? && tag.kind == TokenKind::Conditional {
+					accepted = options.conditions.evaluate(&tag.bytes);
+					*tokens = peek;
+				}
+				if options.infer_types {
+					infer_value(value.bytes)
+				} else {
+					Value::String(to_c_string(value.bytes))
+				}
+			}
+		};
+
+		if accepted {
+			keys.push(KeyValues::with_value(to_c_string(name.bytes), value));
+		}
+	}
+}
+
+/// Returns the value that the engine would parse `bytes` as.
+fn infer_value(bytes: Vec<u8>) -> Value {
+	if bytes.len() == 18 && bytes.starts_with(b"0x")
+		&& let Some(value) = ::core::str::from_utf8(&bytes[2..]).ok().and_then(move |s| u64::from_str_radix(s, 16).ok())
+	{
+		return Value::Uint64(value)
+	}
+
+	if let Ok(s) = ::core::str::from_utf8(&bytes) {
+		let digits = s.strip_prefix(['+', '-']).unwrap_or(s);
+		if !digits.is_empty() && digits.bytes().all(move |c| c.is_ascii_digit()) {
+			if let Ok(value) = s.parse() {
+				return Value::Int(value)
+			}
+		} else if s.bytes().any(move |c| c.is_ascii_digit())
+			&& !s.starts_with(char::is_whitespace)
+			&& let Ok(value) = s.parse()
+		{
+			return Value::Float(value)
+		}
+	}
+
+	Value::String(to_c_string(bytes))
+}
+
+/// Adds keys from `base` that are missing in `key`, recursively.
+///
+/// Nothing is added to `key` if it holds a value instead of subkeys.
+fn merge_missing(key: &mut KeyValues, base: &KeyValues) {
+	let Some(subkeys) = key.subkeys_mut() else { return };
+	for base_sub in base.subkeys() {
+		match subkeys.iter_mut().find(|sub| names_eq(sub.name(), base_sub.name().to_bytes())) {
+			Some(sub) => merge_missing(sub, base_sub),
+			None => subkeys.push(base_sub.clone()),
+		}
+	}
+}
+
+fn write_indent(out: &mut Vec<u8>, level: usize) {
+	out.extend(::core::iter::repeat_n(b'\t', level));
+}
+
+fn write_quoted(out: &mut Vec<u8>, bytes: &[u8], escapes: bool) {
+	out.push(b'"');
+	for &c in bytes {
+		match c {
+			b'"' => out.extend_from_slice(b"\\\""),
+			b'\\' if escapes => out.extend_from_slice(b"\\\\"),
+			b'\n' if escapes => out.extend_from_slice(b"\\n"),
+			b'\t' if escapes => out.extend_from_slice(b"\\t"),
+			_ => out.push(c),
+		}
+	}
+	out.push(b'"');
+}
+
+fn write_key(kv: &KeyValues, out: &mut Vec<u8>, escapes: bool, level: usize) {
+	let text = match kv.value() {
+		Value::Subkeys(subkeys) => {
+			write_indent(out, level);
+			write_quoted(out, kv.name().to_bytes(), escapes);
+			out.push(b'\n');
+			write_indent(out, level);
+			out.extend_from_slice(b"{\n");
+			for sub in subkeys {
+				write_key(sub, out, escapes, level + 1);
+			}
+			write_indent(out, level);
+			out.extend_from_slice(b"}\n");
+			return
+		}
+		Value::String(s) => {
+			write_value_line(out, kv, s.to_bytes(), escapes, level);
+			return
+		}
+		Value::WString(s) => {
+			write_value_line(out, kv, s.as_bytes(), escapes, level);
+			return
+		}
+		Value::Int(i) => format!("{i}"),
+		Value::Float(f) => format!("{f:.6}"),
+		Value::Uint64(u) => format!("0x{u:016X}"),
+		Value::Color(c) => format!("{} {} {} {}", c.r(), c.g(), c.b(), c.a()),
+		Value::Ptr(..) => return,
+	};
+	write_value_line(out, kv, text.as_bytes(), escapes, level)
+}
+
+fn write_value_line(out: &mut Vec<u8>, kv: &KeyValues, value: &[u8], escapes: bool, level: usize) {
+	write_indent(out, level);
+	write_quoted(out, kv.name().to_bytes(), escapes);
+	out.extend_from_slice(b"\t\t");
+	write_quoted(out, value, escapes);
+	out.push(b'\n');
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const BLANK_VDF: &[u8] = include_bytes!("../../../examples/blank/blank.vdf");
+	const CONSOLE_VDF: &[u8] = include_bytes!("../../../examples/console/console.vdf");
+
+	#[test]
+	fn plugin_vdf_round_trip() {
+		for (text, file) in [(BLANK_VDF, c"addons/blank"), (CONSOLE_VDF, c"addons/console")] {
+			let keys = KeyValues::parse_text(text, &ParseOptions::default()).unwrap();
+			assert_eq!(keys.len(), 1);
+			assert_eq!(keys[0].name(), c"Plugin");
+			assert_eq!(keys[0].get_string(c"file"), Some(file));
+
+			let written = keys[0].to_text(false);
+			assert_eq!(KeyValues::parse_text(&written, &ParseOptions::default()).unwrap(), keys);
+		}
+	}
+
+	#[test]
+	fn syntax() {
+		let text = br#"
+			// Comment.
+			root
+			{
+				unquoted value // Trailing comment.
+				"escaped" "a\"b\n"
+				"windows"	"yes"	[$WINDOWS]
+				"never"	[$X360]
+				{
+					"a" "b"
+				}
+				"number" "12"
+				"float" "-1.5"
+				"id" "0x0110000100000001"
+			}
+		"#;
+		let options = ParseOptions {
+			escapes: true,
+			infer_types: true,
+			conditions: Conditions {
+				windows: false,
+				..Conditions::host()
+			},
+		};
+		let keys = KeyValues::parse_text(text, &options).unwrap();
+		let root = &keys[0];
+		assert_eq!(root.get_string(c"unquoted"), Some(c"value"));
+		assert_eq!(root.get_string(c"escaped"), Some(c"a\"b\n"));
+		assert_eq!(root.get(c"windows"), None);
+		assert_eq!(root.get(c"never"), None);
+		assert_eq!(root.get(c"number"), Some(&Value::Int(12)));
+		assert_eq!(root.get(c"float"), Some(&Value::Float(-1.5)));
+		assert_eq!(root.get(c"id"), Some(&Value::Uint64(0x0110000100000001)));
+
+		let written = root.to_text(true);
+		assert_eq!(KeyValues::parse_text(&written, &options).unwrap()[0], *root);
+	}
+
+	#[test]
+	fn includes() {
+		let text = b"#base \"base.txt\"\n#include \"extra.txt\"\n\"root\" { \"a\" \"1\" \"Sub\" { \"x\" \"3\" } \"c\" \"4\" }";
+		let keys = KeyValues::parse_text_with(text, &ParseOptions::default(), move |path| {
+			match path.to_bytes() {
+				b"base.txt" => Some(b"root { A 0 b 2 sub { X 1 y 2 } C { z 5 } } ".to_vec()),
+				b"extra.txt" => Some(b"extra { }".to_vec()),
+				_ => None,
+			}
+		}).unwrap();
+		assert_eq!(keys.len(), 2);
+		assert_eq!(keys[0].get_string(c"a"), Some(c"1"));
+		assert_eq!(keys[0].get_string(c"b"), Some(c"2"));
+		assert_eq!(keys[0].subkeys().len(), 4);
+		// A value isn't replaced by subkeys of the base.
+		assert_eq!(keys[0].get_string(c"c"), Some(c"4"));
+		assert_eq!(keys[0].get_string(c"sub/x"), Some(c"3"));
+		assert_eq!(keys[0].get_string(c"sub/y"), Some(c"2"));
+		assert_eq!(keys[0].find_key(c"sub").unwrap().subkeys().len(), 2);
+		assert_eq!(keys[1].name(), c"extra");
+
+		let error = KeyValues::parse_text(b"#include \"missing.txt\"", &ParseOptions::default()).unwrap_err();
+		assert_eq!(error.kind, ParseErrorKind::IncludeNotFound(c"missing.txt".into()));
+	}
+
+	#[test]
+	fn conditions() {
+		let conditions = Conditions {
+			pc: true,
+			windows: false,
+			linux: true,
+			osx: false,
+			posix: true,
+			console: false,
+		};
+		assert!(conditions.evaluate(b"[$WIN32]"));
+		assert!(conditions.evaluate(b"[!$X360]"));
+		assert!(conditions.evaluate(b"[$WINDOWS||$LINUX]"));
+		assert!(!conditions.evaluate(b"[$POSIX&&$OSX]"));
+	}
+}
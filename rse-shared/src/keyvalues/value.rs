@@ -0,0 +1,221 @@
+use ::alloc::{
+	ffi::CString,
+	string::String,
+	vec::Vec,
+};
+use ::core::ffi::{
+	CStr, c_void,
+};
+use ::rse_math::Color;
+
+use crate::cppdef::KeyValuesType;
+
+use super::KeyValues;
+
+/// Value of a [`KeyValues`] node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+	/// List of subkeys, which may be empty.
+	Subkeys(Vec<KeyValues>),
+	String(CString),
+	Int(i32),
+	Float(f32),
+	/// Opaque pointer that is only meaningful within the current process.
+	Ptr(*mut c_void),
+	WString(String),
+	Color(Color),
+	Uint64(u64),
+}
+
+impl Default for Value {
+	fn default() -> Self {
+		Self::Subkeys(Vec::new())
+	}
+}
+
+impl Value {
+	/// Returns the [`KeyValuesType`] that the engine would use to store this value.
+	pub const fn data_type(&self) -> KeyValuesType {
+		match self {
+			Self::Subkeys(..) => KeyValuesType::None,
+			Self::String(..) => KeyValuesType::String,
+			Self::Int(..) => KeyValuesType::Int,
+			Self::Float(..) => KeyValuesType::Float,
+			Self::Ptr(..) => KeyValuesType::Ptr,
+			Self::WString(..) => KeyValuesType::WString,
+			Self::Color(..) => KeyValuesType::Color,
+			Self::Uint64(..) => KeyValuesType::Uint64,
+		}
+	}
+
+	/// Returns the string if this value is a [`Value::String`].
+	pub fn as_c_str(&self) -> Option<&CStr> {
+		match self {
+			Self::String(s) => Some(s),
+			_ => None,
+		}
+	}
+
+	/// Returns the subkeys if this value is a [`Value::Subkeys`].
+	pub fn as_subkeys(&self) -> Option<&[KeyValues]> {
+		match self {
+			Self::Subkeys(subkeys) => Some(subkeys),
+			_ => None,
+		}
+	}
+
+	/// Returns the value converted to an integer,
+	/// parsing strings like `atoi` does.
+	///
+	/// Returns `None` for subkeys and colors.
+	pub fn to_int(&self) -> Option<i32> {
+		match *self {
+			Self::Int(i) => Some(i),
+			Self::Float(f) => Some(f as _),
+			Self::Uint64(u) => Some(u as _),
+			Self::Ptr(p) => Some(p as usize as _),
+			Self::String(ref s) => Some(parse_int(s.to_bytes()) as _),
+			Self::WString(ref s) => Some(parse_int(s.as_bytes()) as _),
+			Self::Subkeys(..) | Self::Color(..) => None,
+		}
+	}
+
+	/// Returns the value converted to an unsigned 64-bit integer,
+	/// parsing strings like `atoi` does.
+	///
+	/// Returns `None` for subkeys and colors.
+	pub fn to_uint64(&self) -> Option<u64> {
+		match *self {
+			Self::Uint64(u) => Some(u),
+			Self::Int(i) => Some(i as _),
+			Self::Float(f) => Some(f as _),
+			Self::Ptr(p) => Some(p as usize as _),
+			Self::String(ref s) => Some(parse_int(s.to_bytes()) as _),
+			Self::WString(ref s) => Some(parse_int(s.as_bytes()) as _),
+			Self::Subkeys(..) | Self::Color(..) => None,
+		}
+	}
+
+	/// Returns the value converted to a float,
+	/// parsing strings like `atof` does.
+	///
+	/// Returns `None` for subkeys and colors.
+	pub fn to_float(&self) -> Option<f32> {
+		match *self {
+			Self::Float(f) => Some(f),
+			Self::Int(i) => Some(i as _),
+			Self::Uint64(u) => Some(u as _),
+			Self::Ptr(p) => Some(p as usize as _),
+			Self::String(ref s) => Some(parse_float(s.to_bytes())),
+			Self::WString(ref s) => Some(parse_float(s.as_bytes())),
+			Self::Subkeys(..) | Self::Color(..) => None,
+		}
+	}
+
+	/// Returns the value converted to a boolean, where any non-zero integer is `true`.
+	///
+	/// Returns `None` for subkeys and colors.
+	pub fn to_bool(&self) -> Option<bool> {
+		self.to_int().map(move |i| i != 0)
+	}
+
+	/// Returns the value converted to a color,
+	/// parsing strings of up to four space-separated components.
+	///
+	/// Returns `None` for values other than colors and strings.
+	pub fn to_color(&self) -> Option<Color> {
+		match self {
+			Self::Color(c) => Some(*c),
+			Self::String(s) => Some(parse_color(s.to_bytes())),
+			_ => None,
+		}
+	}
+
+	/// Returns the pointer if this value is a [`Value::Ptr`].
+	pub const fn to_ptr(&self) -> Option<*mut c_void> {
+		match *self {
+			Self::Ptr(p) => Some(p),
+			_ => None,
+		}
+	}
+}
+
+macro_rules! value_from {
+	($($ty:ty => $variant:ident),* $(,)?) => {
+		$(
+			impl From<$ty> for Value {
+				fn from(value: $ty) -> Self {
+					Self::$variant(value.into())
+				}
+			}
+		)*
+	};
+}
+value_from! {
+	Vec<KeyValues> => Subkeys,
+	CString => String,
+	&CStr => String,
+	i32 => Int,
+	f32 => Float,
+	*mut c_void => Ptr,
+	Color => Color,
+	u64 => Uint64,
+}
+
+impl From<bool> for Value {
+	fn from(value: bool) -> Self {
+		Self::Int(value as _)
+	}
+}
+
+fn skip_whitespace(s: &[u8]) -> &[u8] {
+	let start = s.iter().position(move |c| !c.is_ascii_whitespace()).unwrap_or(s.len());
+	&s[start..]
+}
+
+fn parse_int(s: &[u8]) -> i64 {
+	let s = skip_whitespace(s);
+	let (negative, s) = match s.first() {
+		Some(b'-') => (true, &s[1..]),
+		Some(b'+') => (false, &s[1..]),
+		_ => (false, s),
+	};
+	let value = s.iter()
+		.take_while(move |c| c.is_ascii_digit())
+		.fold(0i64, move |acc, &c| acc.wrapping_mul(10).wrapping_add((c - b'0') as i64));
+	if negative { value.wrapping_neg() } else { value }
+}
+
+fn parse_float(s: &[u8]) -> f32 {
+	let s = skip_whitespace(s);
+	let len = s.iter()
+		.position(move |c| !matches!(c, b'0'..=b'9' | b'+' | b'-' | b'.' | b'e' | b'E'))
+		.unwrap_or(s.len());
+	// Back off until the longest valid prefix is found, since `atof` ignores trailing garbage.
+	(1..=len).rev()
+		.find_map(move |end| ::core::str::from_utf8(&s[..end]).ok()?.parse().ok())
+		.unwrap_or(0.0)
+}
+
+fn parse_color(s: &[u8]) -> Color {
+	let mut rgba = [0; 4];
+	for (component, part) in rgba.iter_mut().zip(s.split(u8::is_ascii_whitespace).filter(move |p| !p.is_empty())) {
+		*component = parse_float(part) as u8;
+	}
+	Color { rgba }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn conversions() {
+		assert_eq!(Value::from(c" -42xyz").to_int(), Some(-42));
+		assert_eq!(Value::from(c"1.5e1abc").to_float(), Some(15.0));
+		assert_eq!(Value::from(c"255 128 0").to_color(), Some(Color::rgba(255, 128, 0, 0)));
+		assert_eq!(Value::from(2.75f32).to_int(), Some(2));
+		assert_eq!(Value::from(true).to_bool(), Some(true));
+		assert_eq!(Value::default().to_int(), None);
+	}
+}
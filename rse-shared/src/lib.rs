@@ -1,8 +1,18 @@
 #![no_std]
 
+extern crate alloc;
+
 pub mod cppdef;
 
+pub mod bitbuf;
+pub mod entities;
+pub mod keyvalues;
+
+mod collideable;
+pub use collideable::*;
 mod datatable;
 pub use datatable::*;
 mod server_edict;
 pub use server_edict::*;
+mod wide;
+pub use wide::*;
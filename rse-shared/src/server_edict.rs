@@ -1,4 +1,20 @@
-use crate::cppdef::entities::edict_t;
+use ::core::{
+	ffi::{
+		CStr, c_int,
+	},
+	ptr::NonNull,
+};
+use ::rse_cpp::{
+	VtObject, VtObjectMut, VtObjectWrapper, virtual_call,
+};
+
+use crate::{
+	cppdef::entities::{
+		edict_t, BaseEntity, BaseHandle, StateFlags,
+		HandleEntityVt, ServerNetworkableVt, ServerUnknownVt,
+	},
+	Collideable, ServerClass,
+};
 
 pub use crate::cppdef::entities::EdictIndex;
 
@@ -33,4 +49,94 @@ impl ServerEdict {
 	pub const fn index(&self) -> EdictIndex {
 		self.0.base_edict.edict_index
 	}
+
+	pub const fn state_flags(&self) -> StateFlags {
+		self.0.base_edict.state_flags
+	}
+
+	/// Returns `true` if this edict is free for re-use, meaning that it has no entity.
+	pub const fn is_free(&self) -> bool {
+		self.0.base_edict.state_flags.is_free()
+	}
+
+	/// Returns `true` if the entity of this edict is a full server entity,
+	/// as opposed to e.g. a static prop or a temporary entity.
+	pub const fn is_full_entity(&self) -> bool {
+		self.0.base_edict.state_flags.is_full_entity()
+	}
+
+	pub const fn networkable(&self) -> Option<VtObjectMut<ServerNetworkableVt>> {
+		self.0.base_edict.networkable
+	}
+
+	pub const fn unknown(&self) -> Option<VtObjectMut<ServerUnknownVt>> {
+		self.0.base_edict.unknown
+	}
+
+	/// Returns the class name of the entity, e.g. `player` or `prop_physics`.
+	pub fn classname(&self) -> Option<&CStr> {
+		let networkable = unsafe { VtObject::from_ptr_const(self.networkable()?) };
+		let name = unsafe { virtual_call!(networkable => get_class_name()) };
+		(!name.is_null()).then(move || unsafe { CStr::from_ptr(name) })
+	}
+
+	/// Returns the server class of the entity, which describes its networked properties.
+	pub fn server_class(&self) -> Option<&ServerClass> {
+		let networkable = unsafe { VtObject::from_ptr_const(self.networkable()?) };
+		let class = unsafe { virtual_call!(networkable => get_server_class()) };
+		(!class.is_null()).then(move || unsafe { ServerClass::from_ptr(class) })
+	}
+
+	/// Returns the area that the entity is in.
+	pub fn area_num(&self) -> Option<c_int> {
+		let networkable = unsafe { VtObject::from_ptr_const(self.networkable()?) };
+		Some(unsafe { virtual_call!(networkable => area_num()) })
+	}
+
+	/// Returns the `CBaseEntity` of the entity.
+	pub fn base_entity(&self) -> Option<NonNull<BaseEntity>> {
+		let unknown = unsafe { VtObject::from_ptr_const(self.unknown()?) };
+		NonNull::new(unsafe { virtual_call!(unknown => unknown.get_base_entity()) })
+	}
+
+	/// Returns the entity as an `IHandleEntity`, which is what traces report.
+	pub fn handle_entity(&self) -> Option<VtObjectMut<HandleEntityVt>> {
+		self.unknown().map(move |unknown| unknown.cast())
+	}
+
+	/// Returns the handle of the entity.
+	pub fn ref_handle(&self) -> Option<BaseHandle> {
+		let unknown = unsafe { VtObject::from_ptr_const(self.unknown()?) };
+		Some(unsafe { *virtual_call!(unknown => base.get_ref_ehandle()).as_ref() })
+	}
+
+	/// Returns the collision representation of the entity.
+	pub fn collideable(&self) -> Option<&Collideable> {
+		let unknown = unsafe { VtObject::from_ptr_const(self.unknown()?) };
+		let collideable = unsafe { virtual_call!(unknown => unknown.get_collideable()) };
+		Some(Collideable::from_object_const(unsafe { VtObject::from_ptr_const(collideable) }))
+	}
+
+	/// Returns the index of the model of the entity, if it is a full server entity.
+	pub fn model_index(&self) -> Option<c_int> {
+		let entity = unsafe { VtObject::from_ptr_const(self.0.base_edict.server_entity()?) };
+		Some(unsafe { virtual_call!(entity => server_entity.get_model_index()) })
+	}
+
+	/// Returns the name of the model of the entity, if it is a full server entity.
+	pub fn model_name(&self) -> Option<&CStr> {
+		let entity = unsafe { VtObject::from_ptr_const(self.0.base_edict.server_entity()?) };
+		let name = unsafe { virtual_call!(entity => server_entity.get_model_name()) };
+		(!name.as_ptr().is_null()).then(move || unsafe { CStr::from_ptr(name.as_ptr()) })
+	}
+
+	/// Returns `true` if `handle` refers to the entity of this edict,
+	/// and `false` if the handle is stale or refers to another entity.
+	pub const fn is_handle_of(&self, handle: BaseHandle) -> bool {
+		let base = &self.0.base_edict;
+		!self.is_free()
+			&& handle.is_valid()
+			&& handle.entry_index() == base.edict_index as _
+			&& handle.network_serial_number() == base.network_serial_number
+	}
 }
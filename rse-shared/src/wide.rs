@@ -0,0 +1,213 @@
+use ::alloc::{
+	boxed::Box,
+	string::String,
+	vec::Vec,
+};
+use ::core::{
+	fmt,
+	ops::Deref,
+	slice::from_raw_parts,
+};
+
+use crate::cppdef::wchar_t;
+
+/// Borrowed, nul-terminated string of [`wchar_t`]s,
+/// which are UTF-16 code units on Windows, and UTF-32 code points elsewhere.
+#[derive(PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct WideCStr {
+	/// Characters of the string, including the nul terminator.
+	inner: [wchar_t],
+}
+
+impl WideCStr {
+	/// Wraps a raw nul-terminated wide string.
+	///
+	/// # Safety
+	/// `ptr` must point to a nul-terminated wide string that is valid for the lifetime `'a`.
+	pub unsafe fn from_ptr<'a>(ptr: *const wchar_t) -> &'a Self {
+		unsafe {
+			let mut len = 0;
+			while *ptr.add(len) != 0 {
+				len += 1;
+			}
+			Self::from_slice_with_nul_unchecked(from_raw_parts(ptr, len + 1))
+		}
+	}
+
+	/// Wraps `slice` if it ends with the only nul character in it.
+	pub fn from_slice_with_nul(slice: &[wchar_t]) -> Option<&Self> {
+		match slice.iter().position(move |&c| c == 0) {
+			Some(nul) if nul + 1 == slice.len() => Some(unsafe { Self::from_slice_with_nul_unchecked(slice) }),
+			_ => None,
+		}
+	}
+
+	/// # Safety
+	/// `slice` must end with the only nul character in it.
+	pub const unsafe fn from_slice_with_nul_unchecked(slice: &[wchar_t]) -> &Self {
+		unsafe { &*(slice as *const [wchar_t] as *const Self) }
+	}
+
+	pub const fn as_ptr(&self) -> *const wchar_t {
+		self.inner.as_ptr()
+	}
+
+	/// Returns the characters of the string, without the nul terminator.
+	pub const fn as_slice(&self) -> &[wchar_t] {
+		self.inner.split_last().unwrap().1
+	}
+
+	/// Returns the characters of the string, including the nul terminator.
+	pub const fn as_slice_with_nul(&self) -> &[wchar_t] {
+		&self.inner
+	}
+
+	/// Returns the number of [`wchar_t`]s in the string, without the nul terminator.
+	pub const fn len(&self) -> usize {
+		self.inner.len() - 1
+	}
+
+	pub const fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Returns an iterator over the decoded characters of the string,
+	/// replacing invalid ones with [`char::REPLACEMENT_CHARACTER`].
+	pub fn chars_lossy(&self) -> impl Iterator<Item = char> + '_ {
+		decode_wide(self.as_slice().iter().copied())
+	}
+
+	pub fn to_string_lossy(&self) -> String {
+		self.chars_lossy().collect()
+	}
+}
+
+impl fmt::Debug for WideCStr {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Debug::fmt(&self.to_string_lossy(), f)
+	}
+}
+
+impl fmt::Display for WideCStr {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.chars_lossy().try_for_each(move |c| fmt::Write::write_char(f, c))
+	}
+}
+
+impl Default for &WideCStr {
+	fn default() -> Self {
+		const EMPTY: &[wchar_t] = &[0];
+		unsafe { WideCStr::from_slice_with_nul_unchecked(EMPTY) }
+	}
+}
+
+/// Error returned when a string that is converted to a [`WideCString`] contains a nul character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ::thiserror::Error)]
+#[error("nul character found at byte {0} of the string")]
+pub struct InteriorNulError(pub usize);
+
+/// Owned, nul-terminated string of [`wchar_t`]s.
+///
+/// See [`WideCStr`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct WideCString {
+	inner: Box<[wchar_t]>,
+}
+
+impl WideCString {
+	/// Encodes `s` as a wide string.
+	pub fn new(s: &str) -> Result<Self, InteriorNulError> {
+		if let Some(nul) = s.find('\0') {
+			return Err(InteriorNulError(nul))
+		}
+		let mut inner: Vec<wchar_t> = encode_wide(s).collect();
+		inner.push(0);
+		Ok(Self {
+			inner: inner.into_boxed_slice(),
+		})
+	}
+
+	pub fn as_wide_c_str(&self) -> &WideCStr {
+		unsafe { WideCStr::from_slice_with_nul_unchecked(&self.inner) }
+	}
+}
+
+impl Deref for WideCString {
+	type Target = WideCStr;
+	fn deref(&self) -> &Self::Target {
+		self.as_wide_c_str()
+	}
+}
+
+impl AsRef<WideCStr> for WideCString {
+	fn as_ref(&self) -> &WideCStr {
+		self
+	}
+}
+
+impl fmt::Debug for WideCString {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Debug::fmt(self.as_wide_c_str(), f)
+	}
+}
+
+impl From<&WideCStr> for WideCString {
+	fn from(value: &WideCStr) -> Self {
+		Self {
+			inner: value.as_slice_with_nul().into(),
+		}
+	}
+}
+
+impl TryFrom<&str> for WideCString {
+	type Error = InteriorNulError;
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		Self::new(value)
+	}
+}
+
+/// Returns an iterator over the [`wchar_t`]s that encode `s`, without a nul terminator.
+pub fn encode_wide(s: &str) -> impl Iterator<Item = wchar_t> + '_ {
+	#[cfg(windows)]
+	{
+		s.encode_utf16()
+	}
+	#[cfg(not(windows))]
+	{
+		s.chars().map(move |c| c as wchar_t)
+	}
+}
+
+/// Returns an iterator over the characters decoded from `wide`,
+/// replacing invalid ones with [`char::REPLACEMENT_CHARACTER`].
+pub fn decode_wide<I: IntoIterator<Item = wchar_t>>(wide: I) -> impl Iterator<Item = char> {
+	#[cfg(windows)]
+	{
+		char::decode_utf16(wide).map(move |c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+	}
+	#[cfg(not(windows))]
+	{
+		wide.into_iter().map(move |c| char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn round_trip() {
+		let s = WideCString::new("héllo, 世界 🦀").unwrap();
+		assert_eq!(s.to_string_lossy(), "héllo, 世界 🦀");
+		assert_eq!(s.as_slice_with_nul().last(), Some(&0));
+
+		let from_ptr = unsafe { WideCStr::from_ptr(s.as_ptr()) };
+		assert_eq!(from_ptr, &*s);
+		assert_eq!(from_ptr.len(), s.len());
+
+		assert_eq!(WideCString::new("a\0b"), Err(InteriorNulError(1)));
+		assert!(WideCStr::from_slice_with_nul(&[0x61, 0, 0x62, 0]).is_none());
+		assert!(<&WideCStr>::default().is_empty());
+	}
+}
@@ -0,0 +1,141 @@
+use ::darling::{
+	FromDeriveInput, FromField,
+	ast::Data,
+	util::Ignored,
+};
+use ::proc_macro::TokenStream;
+use ::quote::quote;
+use ::syn::{
+	parse, DeriveInput,
+	Ident, Type,
+	Error, Result,
+	ext::IdentExt,
+};
+
+use crate::{
+	DarlCString,
+	c_string_from_string,
+};
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(event), supports(struct_named, struct_unit))]
+struct GameEventInput {
+	ident: Ident,
+	generics: ::syn::Generics,
+	data: Data<Ignored, GameEventField>,
+
+	#[darling(default)]
+	name: Option<DarlCString>,
+	#[darling(default)]
+	server_side: bool,
+}
+
+#[derive(FromField)]
+#[darling(attributes(event))]
+struct GameEventField {
+	ident: Option<Ident>,
+	ty: Type,
+
+	#[darling(default)]
+	key: Option<DarlCString>,
+}
+
+pub fn game_event_impl(input: TokenStream) -> Result<TokenStream> {
+	let input: DeriveInput = parse(input)?;
+	let input = GameEventInput::from_derive_input(&input)?;
+	if !input.generics.params.is_empty() {
+		return Err(Error::new_spanned(&input.generics, "game events can't have generic parameters"))
+	}
+
+	let ident = &input.ident;
+	let name = if let Some(name) = input.name {
+		name.0
+	} else {
+		c_string_from_string(snake_case(&ident.unraw().to_string()))
+	};
+	let server_side = input.server_side;
+
+	let fields = input.data.take_struct().map(move |fields| fields.fields).unwrap_or_default();
+	let field_idents: Vec<_> = fields.iter().map(move |field| field.ident.as_ref().unwrap()).collect();
+	let field_types = fields.iter().map(move |field| &field.ty);
+	let keys: Vec<_> = fields.iter().map(move |field| match field.key {
+		Some(ref key) => key.0.clone(),
+		None => c_string_from_string(field.ident.as_ref().unwrap().unraw().to_string()),
+	}).collect();
+
+	Ok(quote! {
+		#[automatically_derived]
+		impl ::rse_std::sv::Event for #ident {
+			const IS_SERVER_SIDE: bool = #server_side;
+		}
+
+		#[automatically_derived]
+		impl ::rse_std::sv::NamedEvent for #ident {
+			const NAME: &::core::ffi::CStr = #name;
+		}
+
+		#[automatically_derived]
+		impl ::core::convert::TryFrom<&::rse_std::sv::GameEvent> for #ident {
+			type Error = ::rse_std::sv::EventNameMismatch;
+			fn try_from(event: &::rse_std::sv::GameEvent) -> ::core::result::Result<Self, Self::Error> {
+				if ::rse_std::sv::GameEventImpl::name(event) != #name {
+					return ::core::result::Result::Err(::rse_std::sv::EventNameMismatch)
+				}
+				::core::result::Result::Ok(Self {
+					#(
+						#field_idents: <#field_types as ::rse_std::sv::EventField>::read(event, #keys),
+					)*
+				})
+			}
+		}
+
+		#[automatically_derived]
+		impl ::rse_std::sv::EmitEvent for #ident {
+			fn write_fields(&self, event: &mut ::rse_std::sv::GameEvent) {
+				#(
+					::rse_std::sv::EventField::write(&self.#field_idents, event, #keys);
+				)*
+			}
+		}
+	}.into())
+}
+
+/// Converts a `PascalCase` identifier to `snake_case`,
+/// keeping acronyms together, so that `HLTVStatus` becomes `hltv_status`.
+fn snake_case(ident: &str) -> String {
+	let chars: Vec<char> = ident.chars().collect();
+	let mut name = String::with_capacity(ident.len() + 4);
+	for (i, &c) in chars.iter().enumerate() {
+		if c.is_uppercase() {
+			let prev = i.checked_sub(1).map(|i| chars[i]);
+			let next = chars.get(i + 1);
+			let starts_word = match prev {
+				Some(prev) if prev.is_uppercase() => next.is_some_and(|next| next.is_lowercase()),
+				Some(prev) => prev != '_',
+				None => false,
+			};
+			if starts_word {
+				name.push('_');
+			}
+			name.extend(c.to_lowercase());
+		} else {
+			name.push(c);
+		}
+	}
+	name
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn snake_case_names() {
+		assert_eq!(snake_case("PlayerDeath"), "player_death");
+		assert_eq!(snake_case("HLTVStatus"), "hltv_status");
+		assert_eq!(snake_case("PlayerHurtHUD"), "player_hurt_hud");
+		assert_eq!(snake_case("Round2Start"), "round2_start");
+		assert_eq!(snake_case("Player_Spawn"), "player_spawn");
+		assert_eq!(snake_case("teamplay"), "teamplay");
+	}
+}
@@ -0,0 +1,429 @@
+use ::proc_macro::TokenStream;
+use ::proc_macro2::{
+	Span, TokenStream as TokenStream2,
+};
+use ::quote::quote;
+use ::std::{
+	env, fs,
+	path::PathBuf,
+};
+use ::syn::{
+	parse, parse_str, LitStr,
+	Ident,
+	Error, Result,
+};
+
+pub fn game_events_impl(input: TokenStream) -> Result<TokenStream> {
+	let path: LitStr = parse(input)?;
+	let span = path.span();
+
+	let mut full_path = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+	full_path.push(path.value());
+	let text = fs::read(&full_path)
+		.map_err(|e| Error::new(span, format!("couldn't read {}: {e}", full_path.display())))?;
+	let error = |(line, message)| Error::new(span, format!("{}:{line}: {message}", full_path.display()));
+	let events = parse_resource(&text).map_err(error)?;
+	let structs = events.iter()
+		.map(move |event| event_struct(event).map_err(error))
+		.collect::<Result<Vec<_>>>()?;
+	let full_path = full_path.to_string_lossy();
+	Ok(quote! {
+		const _: &[u8] = ::core::include_bytes!(#full_path);
+		#(#structs)*
+	}.into())
+}
+
+fn event_struct(event: &EventDesc) -> ParseResult<TokenStream2> {
+	let name = &event.name;
+	let ident = Ident::new(&pascal_case(name), Span::call_site());
+	let doc = match event.comment {
+		Some(ref comment) => format!(" `{name}` game event: {comment}"),
+		None => format!(" `{name}` game event."),
+	};
+
+	let mut fields = Vec::new();
+	let mut skipped = Vec::new();
+	for field in event.fields.iter() {
+		let key = &field.key;
+		let ty = match field.ty.as_str() {
+			"bool" => quote! { bool },
+			"byte" | "short" | "long" => quote! { ::core::ffi::c_int },
+			"float" => quote! { ::core::ffi::c_float },
+			"uint64" => quote! { u64 },
+			"string" => quote! { ::rse_std::ffi::CString },
+			"wstring" => quote! { ::rse_std::sv::WideCString },
+			// `local` keys hold pointers that are only valid while the event is fired locally,
+			// and `none` keys have no value at all.
+			"local" | "none" => {
+				skipped.push(format!("`{key}` ({})", field.ty));
+				continue
+			}
+			_ => return Err((field.line, format!("unknown type `{}` of key `{key}` in event `{name}`", field.ty))),
+		};
+		let ident = field_ident(key);
+		let doc = field.comment.as_ref().map(move |comment| {
+			let comment = format!(" {comment}");
+			quote! { #[doc = #comment] }
+		});
+		fields.push(quote! {
+			#doc
+			#[event(key = #key)]
+			pub #ident: #ty,
+		});
+	}
+
+	let skipped_doc = (!skipped.is_empty()).then(move || {
+		let note = format!(" The keys {} aren't fields, since they have no value that can be read.", skipped.join(", "));
+		quote! {
+			#[doc = ""]
+			#[doc = #note]
+		}
+	});
+	Ok(quote! {
+		#[doc = #doc]
+		#skipped_doc
+		#[derive(Debug, ::rse_std::GameEvent)]
+		#[event(name = #name, server_side)]
+		pub struct #ident {
+			#(#fields)*
+		}
+	})
+}
+
+/// Converts an event name to a `PascalCase` type name.
+fn pascal_case(name: &str) -> String {
+	let mut ident = String::with_capacity(name.len());
+	let mut upper = true;
+	for c in name.chars() {
+		if c.is_ascii_alphanumeric() {
+			if upper {
+				ident.push(c.to_ascii_uppercase());
+				upper = false;
+			} else {
+				ident.push(c);
+			}
+		} else {
+			upper = true;
+		}
+	}
+	if !ident.starts_with(|c: char| c.is_ascii_alphabetic()) {
+		ident.insert(0, 'E');
+	}
+	ident
+}
+
+/// Converts a key name to a field name, escaping keywords.
+fn field_ident(key: &str) -> Ident {
+	let mut name: String = key.chars()
+		.map(move |c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+		.collect();
+	if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+		name.insert(0, '_');
+	}
+	match name.as_str() {
+		"self" | "super" | "crate" | "_" => Ident::new(&format!("{name}_"), Span::call_site()),
+		_ if parse_str::<Ident>(&name).is_err() => Ident::new_raw(&name, Span::call_site()),
+		_ => Ident::new(&name, Span::call_site()),
+	}
+}
+
+#[derive(Debug)]
+struct EventDesc {
+	name: String,
+	comment: Option<String>,
+	fields: Vec<FieldDesc>,
+}
+
+#[derive(Debug)]
+struct FieldDesc {
+	key: String,
+	ty: String,
+	comment: Option<String>,
+	/// Line of the key in the file.
+	line: usize,
+}
+
+type ParseResult<T> = ::core::result::Result<T, (usize, String)>;
+
+/// Parses the event descriptions in a resource file like `resource/modevents.res`.
+///
+/// Like in the engine, events and keys that are repeated replace the earlier ones,
+/// and are compared case-insensitively.
+fn parse_resource(text: &[u8]) -> ParseResult<Vec<EventDesc>> {
+	let mut tokens = Tokenizer { text, pos: 0, line: 1 };
+	let mut events = Vec::new();
+
+	// Name of the root key, which doesn't matter.
+	tokens.expect_string()?;
+	tokens.expect_open()?;
+	loop {
+		let name = match tokens.next()? {
+			Some(Token::Close) => break,
+			Some(Token::String(name)) => name,
+			_ => return Err((tokens.line, "expected event name".into())),
+		};
+		let comment = tokens.expect_open()?;
+		let mut event = EventDesc { name, comment, fields: Vec::new() };
+		loop {
+			let key = match tokens.next()? {
+				Some(Token::Close) => break,
+				Some(Token::String(key)) => key,
+				_ => return Err((tokens.line, "expected key name".into())),
+			};
+			let ty = tokens.expect_string()?;
+			let field = FieldDesc { key, ty: ty.to_ascii_lowercase(), comment: tokens.trailing_comment(), line: tokens.line };
+			match event.fields.iter_mut().find(|f| f.key.eq_ignore_ascii_case(&field.key)) {
+				Some(existing) => *existing = field,
+				None => event.fields.push(field),
+			}
+		}
+		match events.iter_mut().find(|e: &&mut EventDesc| e.name.eq_ignore_ascii_case(&event.name)) {
+			Some(existing) => *existing = event,
+			None => events.push(event),
+		}
+	}
+	Ok(events)
+}
+
+enum Token {
+	String(String),
+	Open,
+	Close,
+}
+
+struct Tokenizer<'a> {
+	text: &'a [u8],
+	pos: usize,
+	line: usize,
+}
+
+impl Tokenizer<'_> {
+	fn next(&mut self) -> ParseResult<Option<Token>> {
+		loop {
+			self.skip_whitespace();
+			match self.text.get(self.pos) {
+				None => return Ok(None),
+				Some(b'/') if self.text.get(self.pos + 1) == Some(&b'/') => {
+					self.skip_line();
+				}
+				// Conditionals like `[$X360]` are ignored.
+				Some(b'[') => {
+					while let Some(&c) = self.text.get(self.pos) {
+						self.pos += 1;
+						if c == b']' {
+							break
+						}
+					}
+				}
+				Some(b'#') => return Err((self.line, "`#include` and `#base` directives aren't supported".into())),
+				Some(b'{') => {
+					self.pos += 1;
+					return Ok(Some(Token::Open))
+				}
+				Some(b'}') => {
+					self.pos += 1;
+					return Ok(Some(Token::Close))
+				}
+				Some(b'"') => {
+					self.pos += 1;
+					let start = self.pos;
+					loop {
+						match self.text.get(self.pos) {
+							None => return Err((self.line, "unterminated quoted string".into())),
+							Some(b'"') => break,
+							Some(b'\n') => {
+								self.line += 1;
+								self.pos += 1;
+							}
+							Some(_) => self.pos += 1,
+						}
+					}
+					let s = String::from_utf8_lossy(&self.text[start..self.pos]).into_owned();
+					self.pos += 1;
+					return Ok(Some(Token::String(s)))
+				}
+				Some(_) => {
+					let start = self.pos;
+					while let Some(&c) = self.text.get(self.pos) {
+						if c.is_ascii_whitespace() || matches!(c, b'{' | b'}' | b'"') {
+							break
+						}
+						self.pos += 1;
+					}
+					return Ok(Some(Token::String(String::from_utf8_lossy(&self.text[start..self.pos]).into_owned())))
+				}
+			}
+		}
+	}
+
+	fn expect_string(&mut self) -> ParseResult<String> {
+		match self.next()? {
+			Some(Token::String(s)) => Ok(s),
+			None => Err((self.line, "unexpected end of file".into())),
+			_ => Err((self.line, "expected string".into())),
+		}
+	}
+
+	/// Expects `{`, returning the comment on the line before it, if any.
+	fn expect_open(&mut self) -> ParseResult<Option<String>> {
+		let comment = self.trailing_comment();
+		match self.next()? {
+			Some(Token::Open) => Ok(comment),
+			None => Err((self.line, "unexpected end of file".into())),
+			_ => Err((self.line, "expected `{`".into())),
+		}
+	}
+
+	/// Returns the `//` comment that follows on the current line, if any.
+	fn trailing_comment(&mut self) -> Option<String> {
+		while let Some(&c) = self.text.get(self.pos) {
+			if c == b'\n' || !c.is_ascii_whitespace() {
+				break
+			}
+			self.pos += 1;
+		}
+		if self.text[self.pos..].starts_with(b"//") {
+			let start = self.pos + 2;
+			self.skip_line();
+			let comment = String::from_utf8_lossy(&self.text[start..self.pos]);
+			let comment = comment.trim();
+			(!comment.is_empty()).then(|| comment.to_owned())
+		} else {
+			None
+		}
+	}
+
+	fn skip_whitespace(&mut self) {
+		while let Some(&c) = self.text.get(self.pos) {
+			if !c.is_ascii_whitespace() {
+				break
+			}
+			if c == b'\n' {
+				self.line += 1;
+			}
+			self.pos += 1;
+		}
+	}
+
+	fn skip_line(&mut self) {
+		while let Some(&c) = self.text.get(self.pos) {
+			if c == b'\n' {
+				break
+			}
+			self.pos += 1;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SAMPLE: &[u8] = br#"//=========== modevents.res ===========
+// Comment before the root key.
+"ModEvents"
+{
+	"player_death"	// A player died.
+	{
+		"userid"	"short"		// Victim.
+		"attacker"	"SHORT"
+		"weapon"	"string"	// Weapon name.
+		"assister"	"short"	[$X360]
+		"userid"	"byte"
+		"entity"	"local"
+	}
+
+	round_start
+	{
+		timelimit	long
+	}
+
+	"Player_Death"
+	{
+		"userid"	"short"
+		"headshot"	"bool"
+	}
+}
+"#;
+
+	#[test]
+	fn parse_sample() {
+		let events = parse_resource(SAMPLE).unwrap();
+		assert_eq!(events.len(), 2);
+
+		// The second definition replaces the first one in place.
+		let death = &events[0];
+		assert_eq!(death.name, "Player_Death");
+		assert_eq!(death.comment, None);
+		let keys: Vec<_> = death.fields.iter().map(|f| (f.key.as_str(), f.ty.as_str())).collect();
+		assert_eq!(keys, [("userid", "short"), ("headshot", "bool")]);
+
+		let round = &events[1];
+		assert_eq!(round.name, "round_start");
+		assert_eq!(round.fields[0].key, "timelimit");
+		assert_eq!(round.fields[0].ty, "long");
+		assert_eq!(round.fields[0].line, 17);
+	}
+
+	#[test]
+	fn parse_duplicate_keys() {
+		let text = br#""e" { "a" { "x" "short" // first
+			"y" "float" "X" "BYTE" [$X360] "z" "local" } }"#;
+		let events = parse_resource(text).unwrap();
+		let fields = &events[0].fields;
+		assert_eq!(fields.len(), 3);
+		assert_eq!((fields[0].key.as_str(), fields[0].ty.as_str()), ("X", "byte"));
+		assert_eq!(fields[0].comment, None);
+		assert_eq!(fields[0].line, 2);
+		assert_eq!(fields[1].key, "y");
+		assert_eq!(fields[2].ty, "local");
+	}
+
+	#[test]
+	fn parse_errors() {
+		let truncated = &SAMPLE[..SAMPLE.windows(11).position(|w| w == b"round_start").unwrap()];
+		assert!(parse_resource(truncated).is_err());
+		assert_eq!(parse_resource(b"\"e\"\n{\n\"a\"\n[x]\n\"b\"").unwrap_err().0, 5);
+		assert_eq!(parse_resource(b"#base \"other.res\"").unwrap_err().0, 1);
+		assert_eq!(parse_resource(b"e { a {\n\"x\" \"short\n}}").unwrap_err().0, 3);
+	}
+
+	#[test]
+	fn tokenize() {
+		let mut tokens = Tokenizer { text: b"key\t\"quoted value\"// comment\n{[$WIN32]}\"a\"b", pos: 0, line: 1 };
+		assert!(matches!(tokens.next(), Ok(Some(Token::String(ref s))) if s == "key"));
+		assert!(matches!(tokens.next(), Ok(Some(Token::String(ref s))) if s == "quoted value"));
+		assert_eq!(tokens.trailing_comment().as_deref(), Some("comment"));
+		assert!(matches!(tokens.next(), Ok(Some(Token::Open))));
+		assert_eq!(tokens.line, 2);
+		assert!(matches!(tokens.next(), Ok(Some(Token::Close))));
+		assert!(matches!(tokens.next(), Ok(Some(Token::String(ref s))) if s == "a"));
+		assert!(matches!(tokens.next(), Ok(Some(Token::String(ref s))) if s == "b"));
+		assert!(matches!(tokens.next(), Ok(None)));
+	}
+
+	#[test]
+	fn names() {
+		assert_eq!(pascal_case("player_death"), "PlayerDeath");
+		assert_eq!(pascal_case("teamplay_round_start"), "TeamplayRoundStart");
+		assert_eq!(pascal_case("hltv_status"), "HltvStatus");
+		assert_eq!(pascal_case("2fort_capture"), "E2fortCapture");
+		assert_eq!(field_ident("userid").to_string(), "userid");
+		assert_eq!(field_ident("Type").to_string(), "r#type");
+		assert_eq!(field_ident("self").to_string(), "self_");
+		assert_eq!(field_ident("team-id").to_string(), "team_id");
+	}
+
+	#[test]
+	fn unknown_type() {
+		let events = parse_resource(b"e {\n a {\n x vector\n }\n}").unwrap();
+		let (line, message) = event_struct(&events[0]).unwrap_err();
+		assert_eq!(line, 3);
+		assert!(message.contains("`vector`"));
+
+		let events = parse_resource(b"e { a { x local y short } }").unwrap();
+		let tokens = event_struct(&events[0]).unwrap().to_string();
+		assert!(tokens.contains("`x` (local)"));
+		assert!(!tokens.contains("pub x"));
+	}
+}
@@ -22,6 +22,10 @@ mod cvar_value;
 use cvar_value::*;
 mod darling_helpers;
 use darling_helpers::*;
+mod game_event;
+use game_event::*;
+mod game_events;
+use game_events::*;
 use infallible_c_string::*;
 
 #[proc_macro]
@@ -33,6 +37,29 @@ pub fn cvar_value_detail(input: TokenStream) -> TokenStream {
 	}
 }
 
+#[proc_macro_derive(GameEvent, attributes(event))]
+pub fn game_event(input: TokenStream) -> TokenStream {
+	match game_event_impl(input) {
+		Ok(s) => s,
+		Err(e) => e.into_compile_error().into(),
+	}
+}
+
+/// Generates a `#[derive(GameEvent)]` struct for every event in a resource file like `resource/modevents.res`,
+/// with the path relative to the directory of the crate's manifest.
+///
+/// Keys of type `local` or `none` aren't included, since they have no networked value,
+/// but are listed in the documentation of the struct.
+/// Other unknown types are an error.
+/// Events and keys that are repeated replace the earlier ones, like in the engine.
+#[proc_macro]
+pub fn game_events(input: TokenStream) -> TokenStream {
+	match game_events_impl(input) {
+		Ok(s) => s,
+		Err(e) => e.into_compile_error().into(),
+	}
+}
+
 #[proc_macro_attribute]
 pub fn con_command(args: TokenStream, item: TokenStream) -> TokenStream {
 	match con_command_impl(args, item) {
@@ -0,0 +1,40 @@
+use ::core::ffi::CStr;
+use ::rse_shared::keyvalues::{
+	KeyValues, ParseError, ParseOptions,
+};
+use rust_alloc::{
+	ffi::CString,
+	vec::Vec,
+};
+
+use super::read;
+
+#[derive(Debug, ::thiserror::Error)]
+pub enum ReadKeyValuesError {
+	#[error("couldn't read file")]
+	Read,
+	#[error(transparent)]
+	Parse(#[from] ParseError),
+}
+
+/// Reads and parses the KeyValues text file at `path` in the search path `path_id`,
+/// returning its top-level keys.
+///
+/// Files named by `#include` and `#base` directives are looked up relative to the directory of `path`,
+/// in the same search path.
+pub fn read_key_values(
+	path: &CStr, path_id: &CStr, options: &ParseOptions,
+) -> Result<Vec<KeyValues>, ReadKeyValuesError> {
+	let text = read(path, path_id).ok_or(ReadKeyValuesError::Read)?;
+	let path = path.to_bytes();
+	let dir = match path.iter().rposition(move |&c| c == b'/' || c == b'\\') {
+		Some(i) => &path[..=i],
+		None => &[],
+	};
+	let keys = KeyValues::parse_text_with(&text, options, move |include| {
+		let mut full_path = dir.to_vec();
+		full_path.extend_from_slice(include.to_bytes());
+		read(&CString::new(full_path).ok()?, path_id)
+	})?;
+	Ok(keys)
+}
@@ -2,17 +2,38 @@ use ::core::ffi::CStr;
 use ::rse_game_interfaces::{
 	BaseFileSystem, BaseFileSystemImpl,
 };
-use ::std::sync::OnceLock;
+use ::std::{
+	io::{
+		Read, Write,
+	},
+	sync::OnceLock,
+};
+use rust_alloc::vec::Vec;
 
 use crate::plugin::PluginFactories;
 
 mod file;
 pub use file::*;
+mod key_values;
+pub use key_values::*;
 
 pub fn size_of_file(path: &CStr, path_id: &CStr) -> usize {
 	with_fs(move |fs| fs.size_at(path, path_id)) as _
 }
 
+/// Reads the whole file at `path` in the search path `path_id`.
+pub fn read(path: &CStr, path_id: &CStr) -> Option<Vec<u8>> {
+	let mut file = File::open(path, c"rb", path_id)?;
+	let mut data = Vec::with_capacity(file.size());
+	file.read_to_end(&mut data).ok()?;
+	Some(data)
+}
+
+/// Writes `data` to the file at `path` in the search path `path_id`, replacing its contents.
+pub fn write(path: &CStr, path_id: &CStr, data: &[u8]) -> bool {
+	File::open(path, c"wb", path_id).is_some_and(move |mut file| file.write_all(data).is_ok())
+}
+
 static FS: OnceLock<BaseFileSystem> = OnceLock::new();
 
 pub(crate) fn attach(factories: PluginFactories) -> bool {
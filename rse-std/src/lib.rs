@@ -5,6 +5,9 @@
 )]
 
 extern crate alloc as rust_alloc;
+// Lets derive macros refer to `::rse_std` in tests.
+#[cfg(test)]
+extern crate self as rse_std;
 
 #[doc(hidden)]
 pub use ::rse_plugin;
@@ -12,6 +15,7 @@ pub use ::rse_plugin;
 #[cfg(feature = "macros")]
 pub use ::rse_std_macros::{
 	con_var, con_command,
+	GameEvent, game_events,
 };
 
 #[doc(hidden)]
@@ -85,4 +89,11 @@ pub mod prelude {
 		cvar_value,
 		con_var, con_command,
 	};
+	#[cfg(feature = "sv")]
+	pub use crate::{
+		print_to_chat, print_to_chat_all,
+		print_to_console, print_to_console_all,
+		print_center, print_center_all,
+		print_hint, print_hint_all,
+	};
 }
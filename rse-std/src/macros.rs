@@ -17,3 +17,100 @@ macro_rules! export_plugin {
 		$crate::rse_plugin::export_static_plugin!($crate::plugin::Adapter<$ty> = $crate::plugin::Adapter::new());
 	};
 }
+
+/// Prints a message to the chat of a player, with the arguments formatted like [`format!`].
+///
+/// See [`sv::print_to_chat`](crate::sv::print_to_chat).
+#[cfg(feature = "sv")]
+#[macro_export]
+macro_rules! print_to_chat {
+	($client:expr, $($arg:tt)+) => {
+		$crate::sv::print_to_chat($client, ::core::format_args!($($arg)+))
+	};
+}
+
+/// Prints a message to the chat of all connected players, with the arguments formatted like [`format!`].
+///
+/// See [`sv::print_to_chat_all`](crate::sv::print_to_chat_all).
+#[cfg(feature = "sv")]
+#[macro_export]
+macro_rules! print_to_chat_all {
+	($($arg:tt)+) => {
+		$crate::sv::print_to_chat_all(::core::format_args!($($arg)+))
+	};
+}
+
+/// Prints a line to the console of a player, with the arguments formatted like [`format!`].
+///
+/// See [`sv::print_to_console`](crate::sv::print_to_console).
+#[cfg(feature = "sv")]
+#[macro_export]
+macro_rules! print_to_console {
+	($client:expr) => {
+		$crate::sv::print_to_console($client, ::core::format_args!(""))
+	};
+
+	($client:expr, $($arg:tt)+) => {
+		$crate::sv::print_to_console($client, ::core::format_args!($($arg)+))
+	};
+}
+
+/// Prints a line to the consoles of all connected players, with the arguments formatted like [`format!`].
+///
+/// See [`sv::print_to_console_all`](crate::sv::print_to_console_all).
+#[cfg(feature = "sv")]
+#[macro_export]
+macro_rules! print_to_console_all {
+	() => {
+		$crate::sv::print_to_console_all(::core::format_args!(""))
+	};
+
+	($($arg:tt)+) => {
+		$crate::sv::print_to_console_all(::core::format_args!($($arg)+))
+	};
+}
+
+/// Prints a message to the center of the screen of a player, with the arguments formatted like [`format!`].
+///
+/// See [`sv::print_center`](crate::sv::print_center).
+#[cfg(feature = "sv")]
+#[macro_export]
+macro_rules! print_center {
+	($client:expr, $($arg:tt)+) => {
+		$crate::sv::print_center($client, ::core::format_args!($($arg)+))
+	};
+}
+
+/// Prints a message to the center of the screens of all connected players,
+/// with the arguments formatted like [`format!`].
+///
+/// See [`sv::print_center_all`](crate::sv::print_center_all).
+#[cfg(feature = "sv")]
+#[macro_export]
+macro_rules! print_center_all {
+	($($arg:tt)+) => {
+		$crate::sv::print_center_all(::core::format_args!($($arg)+))
+	};
+}
+
+/// Shows a hint box to a player, with the arguments formatted like [`format!`].
+///
+/// See [`sv::print_hint`](crate::sv::print_hint).
+#[cfg(feature = "sv")]
+#[macro_export]
+macro_rules! print_hint {
+	($client:expr, $($arg:tt)+) => {
+		$crate::sv::print_hint($client, ::core::format_args!($($arg)+))
+	};
+}
+
+/// Shows a hint box to all connected players, with the arguments formatted like [`format!`].
+///
+/// See [`sv::print_hint_all`](crate::sv::print_hint_all).
+#[cfg(feature = "sv")]
+#[macro_export]
+macro_rules! print_hint_all {
+	($($arg:tt)+) => {
+		$crate::sv::print_hint_all(::core::format_args!($($arg)+))
+	};
+}
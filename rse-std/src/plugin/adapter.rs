@@ -166,7 +166,6 @@ where
 		fn server_activate(&mut self, edicts: &mut [ServerEdict], client_max: ClientIndex);
 		fn level_shutdown(&mut self);
 		fn client_active(&mut self, entity: &mut ServerEdict);
-		fn client_disconnect(&mut self, entity: &mut ServerEdict);
 		fn client_put_in_server(&mut self, entity: &mut ServerEdict, player_name: &CStr);
 		fn set_command_client(&mut self, index: ClientIndex);
 		fn client_settings_changed(&mut self, edict: &mut ServerEdict);
@@ -176,20 +175,52 @@ where
 			name: &CStr, address: &CStr,
 			reject_reason: RejectReason<'_>,
 		) -> ClientConnect;
-		fn client_command(&mut self, entity: &mut ServerEdict, invocation: &Invocation) -> PluginResult;
 		fn network_id_validated(&mut self, user_name: &CStr, network_id: &CStr) -> PluginResult;
-		fn on_query_cvar_value_finished(
-			&mut self,
-			cookie: QueryCvarCookie,
-			player_entity: &mut ServerEdict,
-			status: QueryCvarValueStatus,
-			cvar_name: &CStr, cvar_value: &CStr,
-		);
 		fn on_edict_allocated(&mut self, edict: &mut ServerEdict);
 		fn on_edict_freed(&mut self, edict: &ServerEdict);
 	}
 	
 	fn game_frame(&mut self, simulating: bool) {
+		#[cfg(feature = "sv")]
+		{
+			crate::sv::expire_cvar_queries();
+			crate::sv::expire_dialogs();
+		}
 		unsafe { self.plugin_mut_unchecked().game_frame(simulating) }
 	}
+
+	fn client_disconnect(&mut self, entity: &mut ServerEdict) {
+		#[cfg(feature = "sv")]
+		{
+			crate::sv::forget_dialogs(entity.index() as _);
+			crate::sv::forget_cvar_queries(entity.index() as _);
+		}
+		unsafe { self.plugin_mut_unchecked().client_disconnect(entity) }
+	}
+
+	fn client_command(&mut self, entity: &mut ServerEdict, invocation: &Invocation) -> PluginResult {
+		#[cfg(feature = "sv")]
+		if crate::sv::handle_dialog_command(entity.index() as _, invocation) {
+			return PluginResult::Stop
+		}
+		unsafe { self.plugin_mut_unchecked().client_command(entity, invocation) }
+	}
+
+	fn on_query_cvar_value_finished(
+		&mut self,
+		cookie: QueryCvarCookie,
+		player_entity: &mut ServerEdict,
+		status: QueryCvarValueStatus,
+		cvar_name: &CStr, cvar_value: &CStr,
+	) {
+		#[cfg(feature = "sv")]
+		if crate::sv::finish_cvar_query(cookie, player_entity.index() as _, status, cvar_value) {
+			return
+		}
+		unsafe {
+			self.plugin_mut_unchecked().on_query_cvar_value_finished(
+				cookie, player_entity, status, cvar_name, cvar_value,
+			)
+		}
+	}
 }
@@ -0,0 +1,80 @@
+use ::core::ffi::CStr;
+use ::rse_game_interfaces::{
+	BotManagerImpl, VEngineServerImpl,
+	PlayerInfoManagerImpl, PlayerInfo2Impl,
+};
+use rust_alloc::{
+	ffi::CString,
+	format,
+};
+
+use crate::plugin::ClientIndex;
+
+use super::{
+	BOT_MANAGER, PLAYER_INFO,
+	read, read_from, read_optional, user_id_of,
+};
+
+pub use ::rse_game_interfaces::{
+	BotController, BotControllerImpl, BotCmdExt, Buttons,
+};
+
+/// Creates a fake client named `name` with the engine,
+/// returning its entity index, or `None` if there are no free player slots.
+/// 
+/// The game DLL doesn't control fake clients,
+/// and they can't be moved with [`with_bot_controller`].
+pub fn create_fake_client(name: &CStr) -> Option<ClientIndex> {
+	read(move |srv| Some(srv?.create_fake_client(name)?.index() as _))
+}
+
+/// Creates a bot named `name` with the game DLL,
+/// returning its entity index, or `None` if it couldn't be created.
+/// 
+/// Bots created with this function can be controlled with [`with_bot_controller`].
+/// This requires the bot manager interface, which not every game implements.
+pub fn create_bot(name: &CStr) -> Option<ClientIndex> {
+	read_optional(&BOT_MANAGER, move |bots| Some(bots.create_bot(name)?.index() as _)).flatten()
+}
+
+/// Calls `f` with the controller of the bot with the entity index `client`,
+/// returning `None` if it isn't a bot created with [`create_bot`].
+pub fn with_bot_controller<F: FnOnce(&BotController) -> R, R>(client: ClientIndex, f: F) -> Option<R> {
+	read(move |srv| {
+		let edict = srv?.edict(client)?;
+		read_optional(&BOT_MANAGER, move |bots| bots.bot_controller_for(edict).map(f)).flatten()
+	})
+}
+
+/// Sets the value of the ConVar named `name` of the fake client with the entity index `client`,
+/// which is how bots are configured, e.g. with `cl_team` or `name`.
+/// 
+/// Returns `false` if there is no such client.
+pub fn set_fake_client_con_var(client: ClientIndex, name: &CStr, value: &CStr) -> bool {
+	read(move |srv| {
+		let Some(srv) = srv else { return false };
+		let Some(edict) = srv.edict(client) else { return false };
+		srv.set_fake_client_con_var_value(edict, name, value);
+		true
+	})
+}
+
+/// Removes the bot with the entity index `client` from the server by kicking it.
+/// 
+/// Returns `false` if there is no such player, or if it isn't a bot or fake client.
+pub fn remove_bot(client: ClientIndex) -> bool {
+	if !is_fake(client) {
+		return false
+	}
+	let Some(user_id) = user_id_of(client) else { return false };
+	super::execute(&CString::new(format!("kickid {user_id}\n")).unwrap_or_default());
+	true
+}
+
+/// Returns `true` if the player with the entity index `client` is a bot or fake client.
+fn is_fake(client: ClientIndex) -> bool {
+	read(move |srv| read_from(&PLAYER_INFO, move |pim| {
+		let (Some(srv), Some(pim)) = (srv, pim) else { return false };
+		srv.edict(client).and_then(move |edict| pim.player_info(edict)).is_some_and(move |info| info.is_fake_client())
+	}))
+}
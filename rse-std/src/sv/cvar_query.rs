@@ -0,0 +1,160 @@
+use ::core::{
+	cell::RefCell,
+	ffi::{
+		CStr, c_float,
+	},
+};
+use ::rse_game_interfaces::VEngineServerImpl;
+use ::rse_plugin::ServerPluginHelpersImpl;
+use rust_alloc::{
+	boxed::Box,
+	ffi::CString,
+	vec::Vec,
+};
+
+use crate::{
+	plugin::{
+		ClientIndex, QueryCvarCookie, QueryCvarValueStatus,
+	},
+	threads::MainThreadBound,
+};
+
+use super::{
+	PLUGIN_HELPERS,
+	read, read_optional, system_time,
+};
+
+/// Default number of seconds that a [`CvarQuery`] waits for the client to respond.
+pub const DEFAULT_CVAR_QUERY_TIMEOUT: c_float = 10.0;
+
+type ResultCallback = Box<dyn FnOnce(ClientIndex, Result<&CStr, QueryCvarValueStatus>)>;
+type TimeoutCallback = Box<dyn FnOnce(ClientIndex)>;
+
+/// Query that was started and that the client hasn't responded to yet.
+struct PendingQuery {
+	cookie: QueryCvarCookie,
+	client: ClientIndex,
+	deadline: c_float,
+	on_result: ResultCallback,
+	on_timeout: Option<TimeoutCallback>,
+}
+
+static PENDING: MainThreadBound<RefCell<Vec<PendingQuery>>> =
+	MainThreadBound::new(RefCell::new(Vec::new()));
+
+pub(super) fn reset() {
+	if let Some(mut pending) = PENDING.get().and_then(move |cell| cell.try_borrow_mut().ok()) {
+		pending.clear();
+	}
+}
+
+/// Forgets all queries sent to `client`, without calling their callbacks.
+pub(crate) fn forget_cvar_queries(client: ClientIndex) {
+	if let Some(mut pending) = PENDING.get().and_then(move |cell| cell.try_borrow_mut().ok()) {
+		pending.retain(move |query| query.client != client);
+	}
+}
+
+/// Calls the result callback of the query with `cookie`,
+/// returning `true` if the query was started with [`CvarQuery`].
+pub(crate) fn finish_cvar_query(
+	cookie: QueryCvarCookie, client: ClientIndex,
+	status: QueryCvarValueStatus, value: &CStr,
+) -> bool {
+	let query = PENDING.get().and_then(move |cell| {
+		let mut pending = cell.try_borrow_mut().ok()?;
+		let index = pending.iter().position(move |query| query.cookie == cookie && query.client == client)?;
+		Some(pending.swap_remove(index))
+	});
+	// The borrow must be released here, since the callback may start another query.
+	let Some(query) = query else { return false };
+	let result = match status {
+		QueryCvarValueStatus::ValueIntact => Ok(value),
+		status => Err(status),
+	};
+	(query.on_result)(client, result);
+	true
+}
+
+/// Calls the timeout callbacks of all queries that the clients didn't respond to in time.
+pub(crate) fn expire_cvar_queries() {
+	let expired: Vec<_> = match PENDING.get().and_then(move |cell| cell.try_borrow_mut().ok()) {
+		Some(mut pending) if !pending.is_empty() => {
+			let now = system_time();
+			pending.extract_if(.., move |query| query.deadline <= now).collect()
+		}
+		_ => return,
+	};
+	for query in expired {
+		if let Some(on_timeout) = query.on_timeout {
+			on_timeout(query.client)
+		}
+	}
+}
+
+/// Cancels the query with `cookie`, so that none of its callbacks are called.
+///
+/// Returns `false` if there was no such query.
+pub fn cancel_cvar_query(cookie: QueryCvarCookie) -> bool {
+	let Some(mut pending) = PENDING.get().and_then(move |cell| cell.try_borrow_mut().ok()) else { return false };
+	let len = pending.len();
+	pending.retain(move |query| query.cookie != cookie);
+	pending.len() != len
+}
+
+/// Builder for a query of the value of a ConVar of a client.
+///
+/// Queries are forgotten without calling any callbacks if the client disconnects.
+pub struct CvarQuery {
+	name: CString,
+	timeout: c_float,
+	on_timeout: Option<TimeoutCallback>,
+}
+
+impl CvarQuery {
+	/// Returns a new query for the ConVar named `name`,
+	/// with a timeout of [`DEFAULT_CVAR_QUERY_TIMEOUT`].
+	pub fn new(name: impl Into<CString>) -> Self {
+		Self {
+			name: name.into(),
+			timeout: DEFAULT_CVAR_QUERY_TIMEOUT,
+			on_timeout: None,
+		}
+	}
+
+	/// Returns `self` with `timeout` as the number of seconds to wait for the client to respond.
+	pub fn timeout(mut self, timeout: c_float) -> Self {
+		self.timeout = timeout;
+		self
+	}
+
+	/// Returns `self` with `f` as the callback that is called with the client's entity index
+	/// if it doesn't respond in time.
+	pub fn on_timeout(mut self, f: impl FnOnce(ClientIndex) + 'static) -> Self {
+		self.on_timeout = Some(Box::new(f));
+		self
+	}
+
+	/// Sends the query to the player with the entity index `client`,
+	/// calling `on_result` with the player's entity index and the value of the ConVar when they respond.
+	///
+	/// Returns `None` if the query couldn't be started.
+	pub fn send(
+		self, client: ClientIndex,
+		on_result: impl FnOnce(ClientIndex, Result<&CStr, QueryCvarValueStatus>) + 'static,
+	) -> Option<QueryCvarCookie> {
+		let cookie = read(|srv| {
+			let edict = srv?.edict(client)?;
+			read_optional(&PLUGIN_HELPERS, move |helpers| helpers.start_query_cvar_value(edict, &self.name))?
+		})?;
+		let mut pending = PENDING.get()?.try_borrow_mut().ok()?;
+		pending.push(PendingQuery {
+			cookie,
+			client,
+			deadline: system_time() + self.timeout,
+			on_result: Box::new(on_result),
+			on_timeout: self.on_timeout,
+		});
+		Some(cookie)
+	}
+}
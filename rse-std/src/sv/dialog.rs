@@ -0,0 +1,487 @@
+use ::core::{
+	cell::{
+		Cell, RefCell,
+	},
+	ffi::{
+		CStr, c_float, c_int,
+	},
+};
+use ::rse_cpp::VtObjectMut;
+use ::rse_game_interfaces::{
+	KeyValuesSystemImpl, VEngineServerImpl,
+};
+use ::rse_math::Color;
+use ::rse_plugin::{
+	cppdef::ServerPluginCallbacksVt,
+	ServerPluginHelpersImpl,
+};
+use ::rse_shared::keyvalues::KeyValues;
+use rust_alloc::{
+	boxed::Box,
+	ffi::CString,
+	format,
+	string::{
+		String, ToString,
+	},
+	vec::Vec,
+};
+
+use crate::{
+	con::cmd::Invocation,
+	plugin::ClientIndex,
+	threads::MainThreadBound,
+};
+
+use super::{
+	KEY_VALUES, PLUGIN, PLUGIN_HELPERS,
+	read, read_optional, system_time,
+};
+
+pub use ::rse_plugin::cppdef::DialogType;
+
+/// Maximum number of options in a [`MenuDialog`].
+pub const MAX_MENU_OPTIONS: usize = 8;
+
+/// Maximum number of dialogs with callbacks that are remembered for each client,
+/// after which the callbacks of the oldest ones are forgotten.
+pub const MAX_PENDING_DIALOGS: usize = 16;
+
+/// Number of seconds that a dialog is shown for if no time is given, as in the engine.
+const DEFAULT_DIALOG_TIME: c_int = 10;
+
+/// Number of seconds that the callback of a dialog is kept after the dialog disappears,
+/// since the command of the client may arrive late.
+const DIALOG_EXPIRY_GRACE: c_float = 5.0;
+
+type MenuCallback = Box<dyn FnOnce(ClientIndex, usize)>;
+type EntryCallback = Box<dyn FnOnce(ClientIndex, &CStr)>;
+
+enum Callback {
+	Menu {
+		f: MenuCallback,
+		/// Whether each option that was shown routes to `f`, since the index comes from the client.
+		routed: Vec<bool>,
+	},
+	Entry(EntryCallback),
+}
+
+/// Dialog that was shown with a callback that hasn't been called yet.
+struct PendingDialog {
+	id: u32,
+	client: ClientIndex,
+	/// [`system_time`] after which the callback can't be called anymore.
+	deadline: c_float,
+	callback: Callback,
+}
+
+static PENDING: MainThreadBound<RefCell<Vec<PendingDialog>>> =
+	MainThreadBound::new(RefCell::new(Vec::new()));
+
+static NEXT_ID: MainThreadBound<Cell<u32>> =
+	MainThreadBound::new(Cell::new(0));
+
+pub(super) fn reset() {
+	if let Some(mut pending) = PENDING.get().and_then(move |cell| cell.try_borrow_mut().ok()) {
+		pending.clear();
+	}
+}
+
+/// Forgets the callbacks of all dialogs shown to `client`.
+pub(crate) fn forget_dialogs(client: ClientIndex) {
+	if let Some(mut pending) = PENDING.get().and_then(move |cell| cell.try_borrow_mut().ok()) {
+		pending.retain(move |dialog| dialog.client != client);
+	}
+}
+
+/// Forgets the callbacks of all dialogs that have disappeared.
+pub(crate) fn expire_dialogs() {
+	let expired: Vec<_> = match PENDING.get().and_then(move |cell| cell.try_borrow_mut().ok()) {
+		Some(mut pending) if !pending.is_empty() => {
+			let now = system_time();
+			pending.extract_if(.., move |dialog| dialog.deadline <= now).collect()
+		}
+		_ => return,
+	};
+	// The callbacks are dropped after the borrow is released, since dropping them may show another dialog.
+	drop(expired);
+}
+
+/// Returns the name of the client command that is run to call the callbacks of dialogs.
+///
+/// The name includes the address of the C++ object of the plugin,
+/// so that plugins that use this module don't handle each other's dialogs.
+fn command_name(plugin: VtObjectMut<ServerPluginCallbacksVt>) -> String {
+	format!("rse_dialog_{:x}", plugin.as_ptr() as usize)
+}
+
+/// Calls the callback of the dialog that `invocation` was run for by `client`, if any,
+/// returning `true` if `invocation` was for a dialog of this plugin.
+pub(crate) fn handle_dialog_command(client: ClientIndex, invocation: &Invocation) -> bool {
+	let Some(plugin) = PLUGIN.get().and_then(Cell::get) else { return false };
+	let [name, id, rest @ ..] = invocation.pieces() else { return false };
+	if name.as_c_str().to_bytes() != command_name(plugin).as_bytes() {
+		return false
+	}
+
+	let Some(id) = id.as_c_str().to_str().ok().and_then(move |id| id.parse::<u32>().ok()) else { return true };
+	let dialog = PENDING.get().and_then(move |cell| {
+		let mut pending = cell.try_borrow_mut().ok()?;
+		let index = pending.iter().position(move |dialog| dialog.id == id && dialog.client == client)?;
+		Some(pending.remove(index))
+	});
+	// The borrow must be released here, since the callback may show another dialog.
+	match dialog.map(move |dialog| dialog.callback) {
+		Some(Callback::Menu { f, routed }) => {
+			let option = rest.first().and_then(move |arg| arg.as_c_str().to_str().ok()?.parse::<usize>().ok());
+			if let Some(option) = option.filter(move |&option| routed.get(option) == Some(&true)) {
+				f(client, option)
+			}
+		}
+		Some(Callback::Entry(f)) => {
+			let mut text = Vec::new();
+			for arg in rest {
+				if !text.is_empty() {
+					text.push(b' ');
+				}
+				text.extend_from_slice(arg.as_c_str().to_bytes());
+			}
+			// SAFETY: Arguments don't contain nul bytes.
+			f(client, &unsafe { CString::from_vec_unchecked(text) })
+		}
+		None => {}
+	}
+	true
+}
+
+fn show_dialog(
+	client: ClientIndex, dialog_type: DialogType,
+	build: impl FnOnce(&str) -> KeyValues,
+	callback: Option<Callback>, time: Option<c_int>,
+) -> bool {
+	let Some(plugin) = PLUGIN.get().and_then(Cell::get) else { return false };
+	let Some(next_id) = NEXT_ID.get() else { return false };
+	let id = next_id.get();
+	let kv = build(&format!("{} {id}", command_name(plugin)));
+
+	let shown = read(move |srv| {
+		let Some(edict) = srv.and_then(move |srv| srv.edict(client)) else { return false };
+		read_optional(&PLUGIN_HELPERS, move |helpers| read_optional(&KEY_VALUES, move |system| {
+			helpers.create_message(edict, dialog_type, &mut system.to_engine(&kv), plugin);
+		})).flatten().is_some()
+	});
+
+	if let Some(callback) = callback.filter(move |_| shown) {
+		let Some(mut pending) = PENDING.get().and_then(move |cell| cell.try_borrow_mut().ok()) else { return shown };
+		let oldest = if pending.iter().filter(move |dialog| dialog.client == client).count() >= MAX_PENDING_DIALOGS {
+			pending.iter().position(move |dialog| dialog.client == client).map(|index| pending.remove(index))
+		} else {
+			None
+		};
+		let time = time.unwrap_or(DEFAULT_DIALOG_TIME).clamp(10, 200);
+		pending.push(PendingDialog {
+			id,
+			client,
+			deadline: system_time() + time as c_float + DIALOG_EXPIRY_GRACE,
+			callback,
+		});
+		next_id.set(id.wrapping_add(1));
+		drop(pending);
+		drop(oldest);
+	}
+	shown
+}
+
+/// Fields shared by most types of dialogs.
+#[derive(Debug, Clone)]
+struct Common {
+	title: CString,
+	text: Option<CString>,
+	color: Option<Color>,
+	level: Option<c_int>,
+	time: Option<c_int>,
+}
+
+impl Common {
+	fn new(title: impl Into<CString>) -> Self {
+		Self {
+			title: title.into(),
+			text: None,
+			color: None,
+			level: None,
+			time: None,
+		}
+	}
+
+	fn to_key_values(&self, name: &CStr) -> KeyValues {
+		let mut kv = KeyValues::new(name).with(c"title", self.title.as_c_str());
+		if let Some(ref text) = self.text {
+			kv.set(c"msg", text.as_c_str());
+		}
+		if let Some(color) = self.color {
+			kv.set(c"color", color);
+		}
+		if let Some(level) = self.level {
+			kv.set(c"level", level);
+		}
+		if let Some(time) = self.time {
+			kv.set(c"time", time);
+		}
+		kv
+	}
+}
+
+macro_rules! common_fns {
+	($($ty:ident)*) => {
+		$(impl $ty {
+			/// Returns `self` with `text` as the message of the dialog.
+			pub fn text(mut self, text: impl Into<CString>) -> Self {
+				self.common.text = Some(text.into());
+				self
+			}
+
+			/// Returns `self` with `color` as the color of the title of the dialog.
+			pub fn color(mut self, color: Color) -> Self {
+				self.common.color = Some(color);
+				self
+			}
+
+			/// Returns `self` with `level` as the priority of the dialog.
+			pub fn level(mut self, level: c_int) -> Self {
+				self.common.level = Some(level);
+				self
+			}
+
+			/// Returns `self` with `time` as the number of seconds that the dialog is shown for,
+			/// which is clamped by the engine to be from `10` to `200`.
+			pub fn time(mut self, time: c_int) -> Self {
+				self.common.time = Some(time);
+				self
+			}
+		})*
+	};
+}
+
+/// Builder for a [`DialogType::Msg`] dialog, which only shows its title on the screen.
+#[derive(Debug, Clone)]
+pub struct MessageDialog {
+	common: Common,
+}
+
+impl MessageDialog {
+	pub fn new(title: impl Into<CString>) -> Self {
+		Self {
+			common: Common::new(title),
+		}
+	}
+
+	/// Shows the dialog to the player with the entity index `client`,
+	/// returning `false` if it couldn't be shown.
+	pub fn show(self, client: ClientIndex) -> bool {
+		show_dialog(client, DialogType::Msg, move |_| self.common.to_key_values(c"msg"), None, None)
+	}
+}
+
+/// Builder for a [`DialogType::Text`] dialog, which shows a window with rich text.
+#[derive(Debug, Clone)]
+pub struct TextDialog {
+	common: Common,
+}
+
+impl TextDialog {
+	pub fn new(title: impl Into<CString>, text: impl Into<CString>) -> Self {
+		Self {
+			common: Common::new(title),
+		}.text(text)
+	}
+
+	/// Shows the dialog to the player with the entity index `client`,
+	/// returning `false` if it couldn't be shown.
+	pub fn show(self, client: ClientIndex) -> bool {
+		show_dialog(client, DialogType::Text, move |_| self.common.to_key_values(c"text"), None, None)
+	}
+}
+
+/// Option of a [`MenuDialog`].
+#[derive(Debug, Clone)]
+struct MenuOption {
+	text: CString,
+	command: Option<CString>,
+}
+
+/// Builder for a [`DialogType::Menu`] dialog, which shows a list of numbered options.
+///
+/// Options without a command call the callback given with [`on_select`](Self::on_select).
+pub struct MenuDialog {
+	common: Common,
+	options: Vec<MenuOption>,
+	on_select: Option<MenuCallback>,
+}
+
+impl MenuDialog {
+	pub fn new(title: impl Into<CString>) -> Self {
+		Self {
+			common: Common::new(title),
+			options: Vec::new(),
+			on_select: None,
+		}
+	}
+
+	/// Returns `self` with an option with the label `text` added.
+	///
+	/// Options after the first [`MAX_MENU_OPTIONS`] are ignored.
+	pub fn option(mut self, text: impl Into<CString>) -> Self {
+		self.options.push(MenuOption {
+			text: text.into(),
+			command: None,
+		});
+		self
+	}
+
+	/// Returns `self` with an option with the label `text` added,
+	/// which makes the player run `command` when selected.
+	///
+	/// Options after the first [`MAX_MENU_OPTIONS`] are ignored.
+	pub fn option_command(mut self, text: impl Into<CString>, command: impl Into<CString>) -> Self {
+		self.options.push(MenuOption {
+			text: text.into(),
+			command: Some(command.into()),
+		});
+		self
+	}
+
+	/// Returns `self` with `f` as the callback that is called with the player's entity index
+	/// and the index of the option that they selected.
+	///
+	/// The callback is called at most once,
+	/// and is forgotten after the dialog's [`time`](Self::time) has passed,
+	/// or after [`MAX_PENDING_DIALOGS`] newer dialogs with callbacks were shown to the player.
+	pub fn on_select(mut self, f: impl FnOnce(ClientIndex, usize) + 'static) -> Self {
+		self.on_select = Some(Box::new(f));
+		self
+	}
+
+	/// Shows the dialog to the player with the entity index `client`,
+	/// returning `false` if it couldn't be shown.
+	pub fn show(self, client: ClientIndex) -> bool {
+		let Self { common, options, on_select } = self;
+		let has_callback = on_select.is_some();
+		let time = common.time;
+		let routed = options.iter()
+			.take(MAX_MENU_OPTIONS)
+			.map(move |option| option.command.is_none())
+			.collect();
+		show_dialog(client, DialogType::Menu, move |route| {
+			let mut kv = common.to_key_values(c"menu");
+			for (i, option) in options.into_iter().take(MAX_MENU_OPTIONS).enumerate() {
+				let command = match option.command {
+					Some(command) => command,
+					None if has_callback => CString::new(format!("{route} {i}")).unwrap_or_default(),
+					None => CString::default(),
+				};
+				kv.add_subkey(
+					KeyValues::new(CString::new((i + 1).to_string()).unwrap_or_default())
+						.with(c"msg", option.text)
+						.with(c"command", command)
+				);
+			}
+			kv
+		}, on_select.map(move |f| Callback::Menu { f, routed }), time)
+	}
+}
+
+/// Builder for a [`DialogType::Entry`] dialog, which asks the player to enter text.
+///
+/// The text is either passed as arguments to the command given with [`command`](Self::command),
+/// or to the callback given with [`on_submit`](Self::on_submit).
+pub struct EntryDialog {
+	common: Common,
+	command: Option<CString>,
+	on_submit: Option<EntryCallback>,
+}
+
+impl EntryDialog {
+	pub fn new(title: impl Into<CString>) -> Self {
+		Self {
+			common: Common::new(title),
+			command: None,
+			on_submit: None,
+		}
+	}
+
+	/// Returns `self` with `command` as the command that the player runs with the entered text.
+	pub fn command(mut self, command: impl Into<CString>) -> Self {
+		self.command = Some(command.into());
+		self
+	}
+
+	/// Returns `self` with `f` as the callback that is called with the player's entity index
+	/// and the text that they entered, if no [`command`](Self::command) is given.
+	///
+	/// The callback is called at most once,
+	/// and is forgotten after the dialog's [`time`](Self::time) has passed,
+	/// or after [`MAX_PENDING_DIALOGS`] newer dialogs with callbacks were shown to the player.
+	pub fn on_submit(mut self, f: impl FnOnce(ClientIndex, &CStr) + 'static) -> Self {
+		self.on_submit = Some(Box::new(f));
+		self
+	}
+
+	/// Shows the dialog to the player with the entity index `client`,
+	/// returning `false` if it couldn't be shown.
+	pub fn show(self, client: ClientIndex) -> bool {
+		let Self { common, command, on_submit } = self;
+		let on_submit = on_submit.filter(|_| command.is_none());
+		let has_callback = on_submit.is_some();
+		let time = common.time;
+		show_dialog(client, DialogType::Entry, move |route| {
+			let command = match command {
+				Some(command) => command,
+				None if has_callback => CString::new(route).unwrap_or_default(),
+				None => CString::default(),
+			};
+			common.to_key_values(c"entry").with(c"command", command)
+		}, on_submit.map(Callback::Entry), time)
+	}
+}
+
+/// Builder for a [`DialogType::AskConnect`] dialog,
+/// which asks the player to connect to another server.
+#[derive(Debug, Clone)]
+pub struct AskConnectDialog {
+	address: CString,
+	time: Option<c_int>,
+}
+
+impl AskConnectDialog {
+	/// Returns a new dialog for connecting to `address`, like `127.0.0.1:27015`.
+	pub fn new(address: impl Into<CString>) -> Self {
+		Self {
+			address: address.into(),
+			time: None,
+		}
+	}
+
+	/// Returns `self` with `time` as the number of seconds that the player has to accept.
+	pub fn time(mut self, time: c_int) -> Self {
+		self.time = Some(time);
+		self
+	}
+
+	/// Shows the dialog to the player with the entity index `client`,
+	/// returning `false` if it couldn't be shown.
+	pub fn show(self, client: ClientIndex) -> bool {
+		show_dialog(client, DialogType::AskConnect, move |_| {
+			let mut kv = KeyValues::new(c"connect").with(c"title", self.address);
+			if let Some(time) = self.time {
+				kv.set(c"time", time);
+			}
+			kv
+		}, None, None)
+	}
+}
+
+common_fns! {
+	MessageDialog
+	TextDialog
+	MenuDialog
+	EntryDialog
+}
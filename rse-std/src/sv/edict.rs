@@ -0,0 +1,72 @@
+use ::core::ffi::c_int;
+use ::rse_game_interfaces::VEngineServerImpl;
+
+pub use ::rse_game_interfaces::UserId;
+pub use ::rse_shared::{
+	cppdef::entities::{
+		BaseHandle, MAX_EDICTS,
+	},
+	Collideable, CollideableImpl,
+	EdictIndex, ServerEdict,
+};
+
+use crate::plugin::ClientIndex;
+
+use super::{
+	max_clients, read,
+};
+
+/// Calls `f` with the edict with the entity index `index`,
+/// returning `None` if it is out of range or free.
+pub fn with_edict<F: FnOnce(&ServerEdict) -> R, R>(index: c_int, f: F) -> Option<R> {
+	read(move |srv| srv?.edict(index).map(f))
+}
+
+/// Calls `f` with the edict of the entity that `handle` refers to,
+/// returning `None` if the handle is invalid or stale.
+pub fn with_edict_of_handle<F: FnOnce(&ServerEdict) -> R, R>(handle: BaseHandle, f: F) -> Option<R> {
+	read(move |srv| srv?.edict_of_handle(handle).map(f))
+}
+
+/// Returns the entity index of the entity that `handle` refers to,
+/// or `None` if the handle is invalid or stale.
+pub fn index_of_handle(handle: BaseHandle) -> Option<c_int> {
+	with_edict_of_handle(handle, move |edict| edict.index() as _)
+}
+
+/// Returns `true` if `handle` refers to an entity that still exists.
+pub fn is_handle_valid(handle: BaseHandle) -> bool {
+	index_of_handle(handle).is_some()
+}
+
+/// Returns the user ID of the player with the entity index `client`.
+pub fn user_id_of(client: ClientIndex) -> Option<UserId> {
+	read(move |srv| {
+		let srv = srv?;
+		srv.player_user_id(srv.edict(client)?)
+	})
+}
+
+/// Returns the entity index of the player with the user ID `user_id`.
+pub fn client_of_user_id(user_id: UserId) -> Option<ClientIndex> {
+	read(move |srv| {
+		let srv = srv?;
+		(1..=max_clients()).find(move |&client| {
+			srv.edict(client).and_then(move |edict| srv.player_user_id(edict)) == Some(user_id)
+		})
+	})
+}
+
+/// Calls `f` with every edict that is in use, in order of their entity indices.
+pub fn for_each_edict(mut f: impl FnMut(&ServerEdict)) {
+	read(move |srv| if let Some(srv) = srv {
+		for edict in srv.edicts() {
+			f(edict)
+		}
+	})
+}
+
+/// Returns the number of edicts that are in use.
+pub fn edict_count() -> usize {
+	read(move |srv| srv.map_or(0, move |srv| srv.entity_count()))
+}
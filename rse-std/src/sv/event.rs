@@ -0,0 +1,294 @@
+use ::core::ffi::{
+	CStr, c_int, c_float,
+};
+use ::rse_game_interfaces::GameEventManager2Impl;
+use rust_alloc::{
+	ffi::CString,
+	vec::Vec,
+};
+
+pub use ::rse_game_interfaces::{
+	Event, NamedEvent,
+	EventListener, RawEventListener, EventListenerGuard,
+	GameEvent, GameEventImpl, OwnedGameEvent,
+	RecordedEvent, GameEventObject, EventLogError,
+	replay_events,
+};
+pub use ::rse_shared::{
+	WideCStr, WideCString,
+};
+
+use super::{
+	GAME_EVENTS,
+	read_optional, server_time,
+};
+
+/// Registers `listener` to be called when a server-side event named `name` is fired,
+/// returning a guard that removes it when dropped.
+///
+/// Returns `None` if there is no event named `name`,
+/// or if the game event manager isn't available.
+pub fn listen_for_event<T: RawEventListener>(name: &CStr, listener: T) -> Option<EventListenerGuard<T>> {
+	// SAFETY: The event manager of the engine lives as long as the engine.
+	read_optional(&GAME_EVENTS, move |events| unsafe { events.add_listener(name, true, listener) }).flatten()
+}
+
+/// Registers `listener` to be called when events of its [`Event`](EventListener::Event) type are fired,
+/// returning a guard that removes it when dropped.
+///
+/// See [`listen_for_event`].
+pub fn listen<T>(listener: T) -> Option<EventListenerGuard<T>>
+where
+	T: EventListener,
+	T::Event: NamedEvent,
+{
+	// SAFETY: See `listen_for_event`.
+	read_optional(&GAME_EVENTS, move |events| unsafe {
+		events.add_listener(T::Event::NAME, T::Event::IS_SERVER_SIDE, listener)
+	}).flatten()
+}
+
+/// Creates a new event named `name`, which is freed when dropped unless it is fired.
+///
+/// Returns `None` if there is no event named `name`,
+/// if nothing listens for it and `force` is `false`,
+/// or if the game event manager isn't available.
+pub fn create_event(name: &CStr, force: bool) -> Option<OwnedGameEvent> {
+	// SAFETY: See `listen_for_event`.
+	read_optional(&GAME_EVENTS, move |events| unsafe { events.create_event(name, force) }).flatten()
+}
+
+/// Loads the event descriptions from the resource file at `path`,
+/// returning the number of events that were loaded.
+pub fn load_events_from_file(path: &CStr) -> c_int {
+	read_optional(&GAME_EVENTS, move |events| events.load_events_from_file(path)).unwrap_or(0)
+}
+
+/// Error returned when converting a [`GameEvent`] to a typed event with a different name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ::thiserror::Error)]
+#[error("game event has a different name")]
+pub struct EventNameMismatch;
+
+/// Typed event that can be created and fired.
+///
+/// This is implemented by `#[derive(GameEvent)]`.
+pub trait EmitEvent: NamedEvent {
+	/// Writes the fields of `self` to `event`.
+	fn write_fields(&self, event: &mut GameEvent);
+
+	/// Creates a new event with the fields of `self`.
+	///
+	/// See [`create_event`].
+	fn to_event(&self, force: bool) -> Option<OwnedGameEvent> {
+		let mut event = create_event(Self::NAME, force)?;
+		self.write_fields(&mut event);
+		Some(event)
+	}
+
+	/// Creates a new event with the fields of `self` and fires it,
+	/// returning `false` if nothing listens for it or if firing it failed.
+	fn fire(&self) -> bool {
+		self.to_event(false).is_some_and(OwnedGameEvent::fire)
+	}
+}
+
+/// Type of a field of a game event.
+pub trait EventField: Sized {
+	/// Reads the field `key` from `event`, or a default value if it isn't set.
+	fn read(event: &GameEvent, key: &CStr) -> Self;
+	/// Writes `self` to the field `key` of `event`.
+	fn write(&self, event: &mut GameEvent, key: &CStr);
+}
+
+impl EventField for bool {
+	fn read(event: &GameEvent, key: &CStr) -> Self {
+		event.get_bool_or(key, false)
+	}
+	fn write(&self, event: &mut GameEvent, key: &CStr) {
+		event.set_bool(key, *self)
+	}
+}
+
+impl EventField for c_int {
+	fn read(event: &GameEvent, key: &CStr) -> Self {
+		event.get_int_or(key, 0)
+	}
+	fn write(&self, event: &mut GameEvent, key: &CStr) {
+		event.set_int(key, *self)
+	}
+}
+
+impl EventField for c_float {
+	fn read(event: &GameEvent, key: &CStr) -> Self {
+		event.get_float_or(key, 0.0)
+	}
+	fn write(&self, event: &mut GameEvent, key: &CStr) {
+		event.set_float(key, *self)
+	}
+}
+
+impl EventField for u64 {
+	fn read(event: &GameEvent, key: &CStr) -> Self {
+		event.get_uint64_or(key, 0)
+	}
+	fn write(&self, event: &mut GameEvent, key: &CStr) {
+		event.set_uint64(key, *self)
+	}
+}
+
+impl EventField for CString {
+	fn read(event: &GameEvent, key: &CStr) -> Self {
+		event.get_string_or(key, c"").into()
+	}
+	fn write(&self, event: &mut GameEvent, key: &CStr) {
+		event.set_string(key, self)
+	}
+}
+
+impl EventField for crate::ffi::CString {
+	fn read(event: &GameEvent, key: &CStr) -> Self {
+		event.get_string_or(key, c"").into()
+	}
+	fn write(&self, event: &mut GameEvent, key: &CStr) {
+		event.set_string(key, self.as_c_str())
+	}
+}
+
+impl EventField for WideCString {
+	fn read(event: &GameEvent, key: &CStr) -> Self {
+		let s = event.get_wide_string_or(key, Default::default());
+		WideCString::from(s)
+	}
+	fn write(&self, event: &mut GameEvent, key: &CStr) {
+		event.set_wide_string(key, self)
+	}
+}
+
+/// Field that is `None` if it isn't set.
+impl<T: EventField> EventField for Option<T> {
+	fn read(event: &GameEvent, key: &CStr) -> Self {
+		(!event.is_key_empty(key)).then(move || T::read(event, key))
+	}
+	fn write(&self, event: &mut GameEvent, key: &CStr) {
+		if let Some(value) = self {
+			value.write(event, key)
+		}
+	}
+}
+
+/// Listener that records every event that it is called with, along with the [`server_time`],
+/// before passing it on to the inner listener.
+///
+/// The recorded events can be replayed with [`replay_events`].
+#[derive(Debug, Default)]
+pub struct EventRecorder<T> {
+	inner: T,
+	events: Vec<RecordedEvent>,
+}
+
+impl<T> EventRecorder<T> {
+	pub const fn new(inner: T) -> Self {
+		Self {
+			inner,
+			events: Vec::new(),
+		}
+	}
+
+	pub const fn as_inner(&self) -> &T {
+		&self.inner
+	}
+
+	pub const fn as_inner_mut(&mut self) -> &mut T {
+		&mut self.inner
+	}
+
+	/// Returns the events that were recorded so far.
+	pub fn events(&self) -> &[RecordedEvent] {
+		&self.events
+	}
+
+	/// Removes and returns the events that were recorded so far.
+	pub fn take_events(&mut self) -> Vec<RecordedEvent> {
+		::core::mem::take(&mut self.events)
+	}
+
+	pub fn into_parts(self) -> (T, Vec<RecordedEvent>) {
+		(self.inner, self.events)
+	}
+}
+
+impl<T: RawEventListener> RawEventListener for EventRecorder<T> {
+	fn fire_game_event(&mut self, event: &GameEvent) {
+		self.events.push(RecordedEvent::from_event(event, server_time()));
+		self.inner.fire_game_event(event)
+	}
+}
+
+#[cfg(feature = "fs")]
+#[derive(Debug, ::thiserror::Error)]
+pub enum ReadEventLogError {
+	#[error("couldn't read file")]
+	Read,
+	#[error(transparent)]
+	Parse(#[from] EventLogError),
+}
+
+/// Reads the event log at `path` in the search path `path_id`.
+///
+/// See [`RecordedEvent::parse_log`].
+#[cfg(feature = "fs")]
+pub fn read_event_log(path: &CStr, path_id: &CStr) -> Result<Vec<RecordedEvent>, ReadEventLogError> {
+	let data = crate::fs::read(path, path_id).ok_or(ReadEventLogError::Read)?;
+	Ok(RecordedEvent::parse_log(&data)?)
+}
+
+/// Writes `events` as an event log to `path` in the search path `path_id`.
+///
+/// See [`RecordedEvent::write_log`].
+#[cfg(feature = "fs")]
+pub fn write_event_log(path: &CStr, path_id: &CStr, events: &[RecordedEvent]) -> bool {
+	crate::fs::write(path, path_id, &RecordedEvent::write_log(events))
+}
+
+#[cfg(all(test, feature = "macros"))]
+mod tests {
+	use crate::GameEvent;
+
+	use super::*;
+
+	#[derive(Debug, PartialEq, GameEvent)]
+	#[event(server_side)]
+	struct HLTVStatus {
+		clients: c_int,
+		r#type: c_int,
+		#[event(key = "master")]
+		master_name: CString,
+		proxies: Option<c_int>,
+	}
+
+	#[derive(Debug, PartialEq, GameEvent)]
+	struct RoundStart;
+
+	#[test]
+	fn derive_game_event() {
+		assert_eq!(HLTVStatus::NAME, c"hltv_status");
+		assert_eq!(RoundStart::NAME, c"round_start");
+		assert_eq!((HLTVStatus::IS_SERVER_SIDE, RoundStart::IS_SERVER_SIDE), (true, false));
+
+		let status = HLTVStatus {
+			clients: 12,
+			r#type: 2,
+			master_name: c"relay".into(),
+			proxies: None,
+		};
+		let mut object = GameEventObject::new(RecordedEvent::new(HLTVStatus::NAME, 0.0));
+		status.write_fields(object.as_event_mut());
+		let event = object.as_event();
+		assert_eq!(event.get_int_or(c"type", 0), 2);
+		assert_eq!(event.get_string(c"master"), Some(c"relay"));
+		assert!(event.is_key_empty(c"proxies"));
+		assert_eq!(HLTVStatus::try_from(event), Ok(status));
+
+		assert_eq!(RoundStart::try_from(event), Err(EventNameMismatch));
+	}
+}
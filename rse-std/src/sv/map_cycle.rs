@@ -0,0 +1,305 @@
+use ::core::ffi::CStr;
+use rust_alloc::{
+	ffi::CString,
+	vec::Vec,
+};
+
+use crate::{
+	con::with_cvars,
+	plugin::ClientIndex,
+};
+
+use super::{
+	change_level, current_map, is_map_valid,
+};
+
+/// Name of the ConVar with the path of the map cycle file, which is tried first by [`MapCycle::load`],
+/// like by the engine: first in the `cfg` directory, then in the game directory.
+pub const MAP_CYCLE_FILE_CVAR: &CStr = c"mapcyclefile";
+
+/// Paths of the map cycle file, relative to the game directory, in the order they're tried by [`MapCycle::load`]
+/// if the file named by [`MAP_CYCLE_FILE_CVAR`] doesn't exist.
+pub const MAP_CYCLE_PATHS: [&CStr; 2] = [c"cfg/mapcycle.txt", c"mapcycle.txt"];
+
+/// List of maps that the server cycles through, as in `mapcycle.txt`.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MapCycle {
+	maps: Vec<CString>,
+}
+
+impl MapCycle {
+	pub const fn new() -> Self {
+		Self {
+			maps: Vec::new(),
+		}
+	}
+
+	/// Parses the contents of a map cycle file.
+	///
+	/// Every line that isn't empty or a `//` comment is the name of a map.
+	/// Maps that were already listed, compared case-insensitively, are skipped.
+	/// Map names aren't validated; see [`retain_valid`](Self::retain_valid).
+	pub fn parse(text: &[u8]) -> Self {
+		let mut maps: Vec<CString> = Vec::new();
+		for line in text.split(move |&b| b == b'\n') {
+			let line = match line.windows(2).position(move |w| w == b"//") {
+				Some(comment) => &line[..comment],
+				None => line,
+			};
+			let Some(name) = line.split(move |b| b.is_ascii_whitespace()).find(move |name| !name.is_empty()) else {
+				continue
+			};
+			if maps.iter().any(move |map| map.to_bytes().eq_ignore_ascii_case(name)) {
+				continue
+			}
+			if let Ok(name) = CString::new(name) {
+				maps.push(name);
+			}
+		}
+		Self {
+			maps,
+		}
+	}
+
+	/// Reads and parses the map cycle file at `path` in the search path `path_id`.
+	#[cfg(feature = "fs")]
+	pub fn load_from(path: &CStr, path_id: &CStr) -> Option<Self> {
+		crate::fs::read(path, path_id).map(move |text| Self::parse(&text))
+	}
+
+	/// Reads and parses the map cycle file named by [`MAP_CYCLE_FILE_CVAR`],
+	/// or else the first one of [`MAP_CYCLE_PATHS`] that exists,
+	/// keeping only the valid maps.
+	#[cfg(feature = "fs")]
+	pub fn load() -> Option<Self> {
+		let mut cycle = candidate_paths(map_cycle_file().as_deref())
+			.into_iter()
+			.find_map(move |path| Self::load_from(&path, c"GAME"))?;
+		cycle.retain_valid();
+		Some(cycle)
+	}
+
+	/// Removes all maps that aren't valid according to [`is_map_valid`].
+	pub fn retain_valid(&mut self) {
+		self.maps.retain(move |map| is_map_valid(map))
+	}
+
+	pub fn maps(&self) -> &[CString] {
+		&self.maps
+	}
+	pub fn len(&self) -> usize {
+		self.maps.len()
+	}
+	pub fn is_empty(&self) -> bool {
+		self.maps.is_empty()
+	}
+
+	pub fn push(&mut self, map_name: impl Into<CString>) {
+		self.maps.push(map_name.into())
+	}
+
+	/// Returns the map that comes after `map_name`, compared case-insensitively,
+	/// wrapping around at the end of the cycle.
+	///
+	/// If `map_name` isn't in the cycle, then the first map is returned.
+	pub fn after(&self, map_name: &CStr) -> Option<&CStr> {
+		let next = self.maps.iter()
+			.position(move |map| map.to_bytes().eq_ignore_ascii_case(map_name.to_bytes()))
+			.map_or(0, move |index| (index + 1) % self.maps.len());
+		self.maps.get(next).map(CString::as_c_str)
+	}
+
+	/// Returns the map that comes after the [current map](current_map).
+	pub fn next_map(&self) -> Option<&CStr> {
+		match current_map() {
+			Some(current) => self.after(&current),
+			None => self.maps.first().map(CString::as_c_str),
+		}
+	}
+}
+
+/// Returns the path of the map cycle file in the [`MAP_CYCLE_FILE_CVAR`] ConVar,
+/// or `None` if it doesn't exist or is empty.
+pub fn map_cycle_file() -> Option<CString> {
+	with_cvars(move |cvars| {
+		let path = cvars.find_var(MAP_CYCLE_FILE_CVAR)?.c_str();
+		(!path.is_empty()).then(move || path.into())
+	}).flatten()
+}
+
+/// Returns the paths that are tried by [`MapCycle::load`] in order,
+/// given the value of [`MAP_CYCLE_FILE_CVAR`].
+#[cfg(feature = "fs")]
+fn candidate_paths(file: Option<&CStr>) -> Vec<CString> {
+	let mut paths = Vec::new();
+	if let Some(file) = file {
+		let mut in_cfg = b"cfg/".to_vec();
+		in_cfg.extend_from_slice(file.to_bytes());
+		paths.extend(CString::new(in_cfg).ok());
+		paths.push(file.into());
+	}
+	for path in MAP_CYCLE_PATHS {
+		if !paths.iter().any(move |p| p.to_bytes().eq_ignore_ascii_case(path.to_bytes())) {
+			paths.push(path.into());
+		}
+	}
+	paths
+}
+
+impl FromIterator<CString> for MapCycle {
+	fn from_iter<T: IntoIterator<Item = CString>>(iter: T) -> Self {
+		Self {
+			maps: iter.into_iter().collect(),
+		}
+	}
+}
+
+/// Vote between maps, where every client has at most one vote.
+#[derive(Default, Debug, Clone)]
+pub struct MapVote {
+	options: Vec<CString>,
+	votes: Vec<(ClientIndex, usize)>,
+}
+
+impl MapVote {
+	/// Returns a new vote with the valid maps of `maps` as options.
+	pub fn new<M: Into<CString>>(maps: impl IntoIterator<Item = M>) -> Self {
+		Self {
+			options: maps.into_iter().map(Into::into).filter(move |map| is_map_valid(map)).collect(),
+			votes: Vec::new(),
+		}
+	}
+
+	/// Returns a new vote with up to `count` maps of `cycle` as options,
+	/// starting with the map after the [current map](current_map), which is excluded.
+	pub fn from_cycle(cycle: &MapCycle, count: usize) -> Self {
+		let maps = cycle.maps();
+		let current = current_map();
+		let start = current.as_deref()
+			.and_then(move |current| maps.iter().position(move |map| map.to_bytes().eq_ignore_ascii_case(current.to_bytes())))
+			.map_or(0, move |index| index + 1);
+		let options = maps.iter().cycle().skip(start).take(maps.len())
+			.filter(move |map| current.as_deref().is_none_or(move |current| !map.to_bytes().eq_ignore_ascii_case(current.to_bytes())))
+			.take(count)
+			.cloned();
+		Self::new(options)
+	}
+
+	pub fn options(&self) -> &[CString] {
+		&self.options
+	}
+
+	/// Records the vote of `client` for the option at index `option`,
+	/// replacing its previous vote.
+	///
+	/// Returns `false` if there is no such option.
+	pub fn vote(&mut self, client: ClientIndex, option: usize) -> bool {
+		if option >= self.options.len() {
+			return false
+		}
+		match self.votes.iter_mut().find(move |(voter, _)| *voter == client) {
+			Some((_, vote)) => *vote = option,
+			None => self.votes.push((client, option)),
+		}
+		true
+	}
+
+	/// Removes the vote of `client`, returning `false` if it hasn't voted.
+	pub fn remove_vote(&mut self, client: ClientIndex) -> bool {
+		let len = self.votes.len();
+		self.votes.retain(move |&(voter, _)| voter != client);
+		self.votes.len() != len
+	}
+
+	/// Returns the option that `client` voted for.
+	pub fn vote_of(&self, client: ClientIndex) -> Option<usize> {
+		self.votes.iter().find(move |&&(voter, _)| voter == client).map(move |&(_, option)| option)
+	}
+
+	/// Returns the total number of votes.
+	pub fn vote_count(&self) -> usize {
+		self.votes.len()
+	}
+
+	/// Returns the number of votes for each option.
+	pub fn tally(&self) -> Vec<usize> {
+		let mut tally = rust_alloc::vec![0; self.options.len()];
+		for &(_, option) in self.votes.iter() {
+			tally[option] += 1;
+		}
+		tally
+	}
+
+	/// Returns the option with the most votes, preferring earlier options in case of a tie,
+	/// or `None` if nobody voted.
+	pub fn winner(&self) -> Option<&CStr> {
+		let tally = self.tally();
+		let (index, _) = tally.iter().enumerate()
+			.filter(move |&(_, &votes)| votes > 0)
+			.rev()
+			.max_by_key(move |&(_, &votes)| votes)?;
+		Some(&self.options[index])
+	}
+
+	/// Changes the level to the [winner](Self::winner) of the vote,
+	/// returning `false` if there is none or the level couldn't be changed.
+	pub fn change_level_to_winner(&self) -> bool {
+		self.winner().is_some_and(move |map| change_level(map, None))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rust_alloc::vec;
+
+	use super::*;
+
+	#[test]
+	fn parse_cycle() {
+		let cycle = MapCycle::parse(b"// Map cycle\r\n\ncp_dustbowl\r\n  ctf_2fort // Classic\n\n\t\nCP_Dustbowl\npl_badwater extra\n//pl_upward\n");
+		assert_eq!(cycle.maps(), [c"cp_dustbowl", c"ctf_2fort", c"pl_badwater"]);
+		assert_eq!(cycle.after(c"CTF_2FORT"), Some(c"pl_badwater"));
+		assert_eq!(cycle.after(c"pl_badwater"), Some(c"cp_dustbowl"));
+		assert_eq!(cycle.after(c"koth_harvest"), Some(c"cp_dustbowl"));
+		assert!(MapCycle::parse(b"\n// nothing\n").is_empty());
+	}
+
+	#[cfg(feature = "fs")]
+	#[test]
+	fn load_order() {
+		assert_eq!(candidate_paths(Some(c"mapcycle.txt")), [c"cfg/mapcycle.txt", c"mapcycle.txt"]);
+		assert_eq!(
+			candidate_paths(Some(c"mapcycle_custom.txt")),
+			[c"cfg/mapcycle_custom.txt", c"mapcycle_custom.txt", c"cfg/mapcycle.txt", c"mapcycle.txt"],
+		);
+		assert_eq!(candidate_paths(None), [c"cfg/mapcycle.txt", c"mapcycle.txt"]);
+	}
+
+	#[test]
+	fn vote_winner() {
+		let mut vote = MapVote {
+			options: vec![c"cp_dustbowl".into(), c"ctf_2fort".into(), c"pl_badwater".into()],
+			votes: Vec::new(),
+		};
+		assert_eq!(vote.winner(), None);
+
+		assert!(vote.vote(1, 2));
+		assert!(vote.vote(2, 1));
+		assert!(!vote.vote(3, 3));
+		// Ties are won by the earlier option.
+		assert_eq!(vote.winner(), Some(c"ctf_2fort"));
+
+		assert!(vote.vote(3, 2));
+		assert_eq!(vote.winner(), Some(c"pl_badwater"));
+
+		// Changing a vote replaces it.
+		assert!(vote.vote(3, 0));
+		assert_eq!(vote.tally(), [1, 1, 1]);
+		assert_eq!(vote.winner(), Some(c"cp_dustbowl"));
+
+		assert!(vote.remove_vote(3));
+		assert!(!vote.remove_vote(3));
+		assert_eq!(vote.vote_count(), 2);
+		assert_eq!(vote.winner(), Some(c"ctf_2fort"));
+	}
+}
@@ -0,0 +1,353 @@
+use ::core::{
+	cell::{
+		Cell, RefCell,
+	},
+	ffi::CStr,
+};
+use ::rse_cpp::VtObjectMut;
+use ::rse_game_interfaces::{
+	VEngineServer, VEngineServerImpl,
+	PlayerInfoManager, PlayerInfoManagerImpl,
+	BotManager,
+	ServerGameDll,
+	KeyValuesSystem,
+	EngineTrace,
+	GameEventManager2,
+	InterfaceOfFactory,
+};
+use ::rse_plugin::{
+	cppdef::ServerPluginCallbacksVt,
+	Factory, ServerPluginHelpers,
+};
+use rust_alloc::ffi::CString;
+
+pub use ::rse_game_interfaces::{
+	Model, Decal, Generic,
+};
+pub use ::rse_shared::entities::{
+	self, Entity, EntityLump,
+};
+
+use crate::{
+	c_buffer::CBuffer,
+	plugin::{
+		ClientIndex, PluginFactories,
+	},
+	threads::MainThreadBound,
+};
+
+mod bot;
+pub use bot::*;
+mod cvar_query;
+pub use cvar_query::*;
+mod dialog;
+pub use dialog::*;
+mod edict;
+pub use edict::*;
+mod event;
+pub use event::*;
+mod map_cycle;
+pub use map_cycle::*;
+mod print;
+pub use print::*;
+mod trace;
+pub use trace::*;
+mod visibility;
+pub use visibility::*;
+
+mod user_message;
+pub use user_message::*;
+
+static SERVER: MainThreadBound<RefCell<Option<VEngineServer>>> =
+	MainThreadBound::new(RefCell::new(None));
+
+static PLAYER_INFO: MainThreadBound<RefCell<Option<PlayerInfoManager>>> =
+	MainThreadBound::new(RefCell::new(None));
+
+static GAME_DLL: MainThreadBound<RefCell<Option<ServerGameDll>>> =
+	MainThreadBound::new(RefCell::new(None));
+
+static BOT_MANAGER: MainThreadBound<RefCell<Option<BotManager>>> =
+	MainThreadBound::new(RefCell::new(None));
+
+static PLUGIN_HELPERS: MainThreadBound<RefCell<Option<ServerPluginHelpers>>> =
+	MainThreadBound::new(RefCell::new(None));
+
+static KEY_VALUES: MainThreadBound<RefCell<Option<KeyValuesSystem>>> =
+	MainThreadBound::new(RefCell::new(None));
+
+static ENGINE_TRACE: MainThreadBound<RefCell<Option<EngineTrace>>> =
+	MainThreadBound::new(RefCell::new(None));
+
+static GAME_EVENTS: MainThreadBound<RefCell<Option<GameEventManager2>>> =
+	MainThreadBound::new(RefCell::new(None));
+
+/// C++ object of the plugin that was loaded, used to identify it to the engine.
+static PLUGIN: MainThreadBound<Cell<Option<VtObjectMut<ServerPluginCallbacksVt>>>> =
+	MainThreadBound::new(Cell::new(None));
+
+/// Tries to initialize the interfaces used by the functions in this module.
+/// 
+/// # Safety
+/// This function must be called from the main thread.
+pub(crate) unsafe fn attach(factories: PluginFactories) -> bool {
+	let attached = unsafe { attach_to(&SERVER, factories) && attach_to(&PLAYER_INFO, factories) };
+	if attached {
+		// The version of the game DLL interface differs between games,
+		// so only the functions that use it are unavailable if it's not found.
+		unsafe { attach_to(&GAME_DLL, factories) };
+		// Not every game implements the bot manager.
+		unsafe { attach_to(&BOT_MANAGER, factories) };
+		// These are only needed for dialogs and ConVar queries.
+		unsafe { attach_to(&PLUGIN_HELPERS, factories) };
+		unsafe { attach_to(&KEY_VALUES, factories) };
+		// Only the trace functions are unavailable if this isn't found.
+		unsafe { attach_to(&ENGINE_TRACE, factories) };
+		// Only the game event functions are unavailable if this isn't found.
+		unsafe { attach_to(&GAME_EVENTS, factories) };
+		unsafe { PLUGIN.get_unchecked().set(factories.callbacks()) };
+		user_message::reset_cache();
+		dialog::reset();
+		cvar_query::reset();
+	}
+	attached
+}
+
+/// # Safety
+/// This function must be called from the main thread.
+unsafe fn attach_to<I>(cell: &MainThreadBound<RefCell<Option<I>>>, factories: PluginFactories) -> bool
+where
+	I: InterfaceOfFactory,
+	I::Factory: Factory,
+	I::Error: ::core::fmt::Display,
+{
+	match factories.create_interface() {
+		Ok(iface) => {
+			unsafe { *cell.get_unchecked().try_borrow_mut().unwrap_unchecked() = Some(iface); }
+			true
+		}
+		Err(error) => {
+			::rse_tier0::con_warn!("{error}");
+			false
+		}
+	}
+}
+
+#[cold]
+const fn not_init() -> ! {
+	panic!("server interface used without being initialized")
+}
+
+fn read<F: FnOnce(Option<&VEngineServer>) -> R, R>(f: F) -> R {
+	if let Some(guard) = SERVER.get().and_then(move |cell| cell.try_borrow().ok()) {
+		match *guard {
+			Some(ref srv) => f(Some(srv)),
+			None => not_init(),
+		}
+	} else {
+		f(None)
+	}
+}
+
+fn write<F: FnOnce(Option<&mut VEngineServer>) -> R, R>(f: F) -> R {
+	if let Some(mut guard) = SERVER.get().and_then(move |cell| cell.try_borrow_mut().ok()) {
+		match *guard {
+			Some(ref mut srv) => f(Some(srv)),
+			None => not_init(),
+		}
+	} else {
+		f(None)
+	}
+}
+
+/// # Safety
+/// The operations performed on the interface *must* support multi-threading.
+unsafe fn read_mt<F: FnOnce(&VEngineServer) -> R, R>(f: F) -> R {
+	unsafe {
+		// FIXME: We might need a better alternative to `RefCell` to be able to handle MT properly.
+		// Right now, there is no way to Just Get A Reference to the value wrapped by a `RefCell`
+		// without having to use `Result::unwrap_unchecked` and possibly getting garbage.
+		// Multi-threaded operations effectively bypass the borrow-checking rules;
+		// it might not be possible to represent this behavior with this type.
+		let guard = SERVER.get_unchecked().try_borrow().unwrap_unchecked();
+		match *guard {
+			Some(ref srv) => f(srv),
+			None => not_init(),
+		}
+	}
+}
+
+fn read_from<I, F: FnOnce(Option<&I>) -> R, R>(cell: &MainThreadBound<RefCell<Option<I>>>, f: F) -> R {
+	if let Some(guard) = cell.get().and_then(move |cell| cell.try_borrow().ok()) {
+		match *guard {
+			Some(ref iface) => f(Some(iface)),
+			None => not_init(),
+		}
+	} else {
+		f(None)
+	}
+}
+
+/// Like [`read_from`], but for interfaces that are optional,
+/// returning `None` if the interface isn't available.
+fn read_optional<I, F: FnOnce(&I) -> R, R>(cell: &MainThreadBound<RefCell<Option<I>>>, f: F) -> Option<R> {
+	let guard = cell.get()?.try_borrow().ok()?;
+	guard.as_ref().map(f)
+}
+
+pub fn is_main_thread() -> bool {
+	SERVER.can_be_accessed()
+}
+
+pub fn system_time() -> f32 {
+	unsafe { read_mt(move |srv| srv.system_time()) }
+}
+pub fn is_dedicated() -> bool {
+	unsafe { read_mt(move |srv| srv.is_dedicated_server()) }
+}
+
+/// Returns the maximum number of players on the server.
+pub fn max_clients() -> ClientIndex {
+	read_from(&PLAYER_INFO, move |pim| if let Some(pim) = pim {
+		pim.global_vars().base.max_clients
+	} else {
+		0
+	})
+}
+
+pub fn server_time() -> f32 {
+	read(move |srv| if let Some(srv) = srv {
+		srv.server_time() as _
+	} else {
+		0.0
+	})
+}
+
+pub fn is_paused() -> bool {
+	read(move |srv| if let Some(srv) = srv {
+		srv.is_paused()
+	} else {
+		false
+	})
+}
+pub fn is_map_valid(map_name: &CStr) -> bool {
+	read(move |srv| if let Some(srv) = srv {
+		srv.is_map_valid(map_name)
+	} else {
+		false
+	})
+}
+
+/// Changes the level to `map_name`, returning `false` if it isn't a valid map.
+///
+/// If `landmark` is `Some`, then the level change is a transition,
+/// and the players are placed relative to the landmark entity of the same name.
+pub fn change_level(map_name: &CStr, landmark: Option<&CStr>) -> bool {
+	read(move |srv| match srv {
+		Some(srv) if srv.is_map_valid(map_name) => {
+			srv.change_level(map_name, landmark);
+			true
+		}
+		_ => false,
+	})
+}
+
+/// Returns the name of the map that is currently loaded,
+/// or `None` if no map is loaded.
+pub fn current_map() -> Option<CString> {
+	read_from(&PLAYER_INFO, move |pim| {
+		let name = pim?.global_vars().map_name.to_c_str();
+		(!name.is_empty()).then(move || name.into())
+	})
+}
+
+/// Returns the entities of the current map, as they were in its entity lump,
+/// or `None` if the lump couldn't be parsed.
+pub fn map_entities() -> Option<EntityLump> {
+	read(move |srv| EntityLump::parse(srv?.map_entities_string().to_bytes()).ok())
+}
+
+pub fn execute(command: &CStr) {
+	unsafe { read_mt(move |srv| srv.push_command_back(command)) }
+}
+
+pub fn precache_model(path: &CStr, preload: bool) -> Option<Model> {
+	write(move |srv| if let Some(srv) = srv {
+		srv.precache_model(path, preload)
+	} else {
+		None
+	})
+}
+pub fn precache_sentence_file(path: &CStr, preload: bool) {
+	write(move |srv| if let Some(srv) = srv {
+		srv.precache_sentence_file(path, preload)
+	})
+}
+pub fn precache_decal(path: &CStr, preload: bool) -> Decal {
+	write(move |srv| if let Some(srv) = srv {
+		srv.precache_decal(path, preload)
+	} else {
+		0
+	})
+}
+pub fn precache_generic(path: &CStr, preload: bool) -> Generic {
+	write(move |srv| if let Some(srv) = srv {
+		srv.precache_generic(path, preload)
+	} else {
+		0
+	})
+}
+
+const DEFAULT_PRECACHED: bool = false; // Nah.
+pub fn is_model_precached(path: &CStr) -> bool {
+	read(move |srv| if let Some(srv) = srv {
+		srv.is_model_precached(path)
+	} else {
+		DEFAULT_PRECACHED
+	})
+}
+pub fn is_decal_precached(path: &CStr) -> bool {
+	read(move |srv| if let Some(srv) = srv {
+		srv.is_decal_precached(path)
+	} else {
+		DEFAULT_PRECACHED
+	})
+}
+pub fn is_generic_precached(path: &CStr) -> bool {
+	read(move |srv| if let Some(srv) = srv {
+		srv.is_generic_precached(path)
+	} else {
+		DEFAULT_PRECACHED
+	})
+}
+
+pub fn game_dir() -> GameDir {
+	unsafe {
+		read_mt(move |srv| {
+			let mut dir = GameDir::new();
+			srv.game_dir(dir.buffer.bytes_mut());
+			dir
+		})
+	}
+}
+pub fn game_dir_into(dir: &mut GameDir) {
+	unsafe { read_mt(move |srv| srv.game_dir(dir.buffer.bytes_mut())) }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct GameDir {
+	buffer: CBuffer<{crate::fs_consts::MAX_OSPATH}>,
+}
+
+impl GameDir {
+	pub const fn new() -> Self {
+		Self {
+			buffer: CBuffer::new(),
+		}
+	}
+
+	pub const fn as_c_str(&self) -> &CStr {
+		self.buffer.as_c_str()
+	}
+}
@@ -0,0 +1,133 @@
+use ::core::{
+	ffi::CStr,
+	fmt,
+};
+use ::rse_game_interfaces::{
+	VEngineServerImpl,
+	user_messages::{
+		HintText, HudPrint, SayText, SayText2, TextMsg,
+	},
+};
+use ::rse_math::Color;
+use rust_alloc::{
+	ffi::CString,
+	fmt::format,
+};
+
+use crate::plugin::ClientIndex;
+
+use super::{
+	RecipientFilter,
+	all_players_filter, read, send_message,
+};
+
+/// Chat color code that switches back to the default color.
+pub const CHAT_DEFAULT: &str = "\x01";
+/// Chat color code that switches to the team color of the player that sent the message.
+pub const CHAT_TEAM: &str = "\x03";
+
+/// Chat color code for an arbitrary color, for mods that support it.
+///
+/// Opaque colors are formatted as `\x07RRGGBB`, and other colors as `\x08RRGGBBAA`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ChatColor(pub Color);
+
+impl fmt::Display for ChatColor {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let [r, g, b, a] = self.0.rgba;
+		if a == u8::MAX {
+			write!(f, "\x07{r:02X}{g:02X}{b:02X}")
+		} else {
+			write!(f, "\x08{r:02X}{g:02X}{b:02X}{a:02X}")
+		}
+	}
+}
+
+/// Formats `args` into a C string, dropping any nul bytes.
+fn format_c(args: fmt::Arguments<'_>, newline: bool) -> CString {
+	let mut text = format(args).into_bytes();
+	text.retain(move |&b| b != 0);
+	if newline {
+		text.push(b'\n');
+	}
+	// SAFETY: All nul bytes were removed above.
+	unsafe { CString::from_vec_unchecked(text) }
+}
+
+fn print_console(client: ClientIndex, text: &CStr) -> bool {
+	read(move |srv| {
+		let Some(srv) = srv else { return false };
+		let Some(edict) = srv.edict(client) else { return false };
+		srv.client_printf(edict, text);
+		true
+	})
+}
+
+/// Prints a line to the console of the player with the entity index `client`.
+///
+/// Returns `false` if there is no such player.
+pub fn print_to_console(client: ClientIndex, args: fmt::Arguments<'_>) -> bool {
+	print_console(client, &format_c(args, true))
+}
+
+/// Prints a line to the consoles of all connected players.
+pub fn print_to_console_all(args: fmt::Arguments<'_>) {
+	let text = format_c(args, true);
+	for &client in all_players_filter().recipients() {
+		print_console(client, &text);
+	}
+}
+
+fn print_chat(mut filter: RecipientFilter, author: ClientIndex, text: &CStr) -> bool {
+	let author = author as _;
+	send_message(&mut filter, &SayText2::new(author, true, text))
+		|| send_message(&mut filter, &SayText { client: author, text, chat: true })
+}
+
+/// Prints a message to the chat of the player with the entity index `client`.
+///
+/// The message may contain the color codes [`CHAT_DEFAULT`], [`CHAT_TEAM`] and [`ChatColor`],
+/// where [`CHAT_TEAM`] is the team color of `client`.
+/// Messages that don't fit in a user message aren't sent.
+///
+/// Returns `false` if the message couldn't be sent.
+pub fn print_to_chat(client: ClientIndex, args: fmt::Arguments<'_>) -> bool {
+	print_chat(RecipientFilter::single(client).reliable(), client, &format_c(args, false))
+}
+
+/// Prints a message to the chat of all connected players.
+///
+/// See also [`print_to_chat`].
+pub fn print_to_chat_all(args: fmt::Arguments<'_>) -> bool {
+	print_chat(all_players_filter(), 0, &format_c(args, false))
+}
+
+fn print_text_msg(mut filter: RecipientFilter, dest: HudPrint, text: &CStr) -> bool {
+	send_message(&mut filter, &TextMsg::new(dest, text))
+}
+
+/// Prints a message to the center of the screen of the player with the entity index `client`.
+///
+/// Returns `false` if the message couldn't be sent.
+pub fn print_center(client: ClientIndex, args: fmt::Arguments<'_>) -> bool {
+	print_text_msg(RecipientFilter::single(client).reliable(), HudPrint::Center, &format_c(args, false))
+}
+
+/// Prints a message to the center of the screens of all connected players.
+pub fn print_center_all(args: fmt::Arguments<'_>) -> bool {
+	print_text_msg(all_players_filter(), HudPrint::Center, &format_c(args, false))
+}
+
+/// Shows a hint box to the player with the entity index `client`.
+///
+/// Returns `false` if the message couldn't be sent.
+pub fn print_hint(client: ClientIndex, args: fmt::Arguments<'_>) -> bool {
+	let mut filter = RecipientFilter::single(client).reliable();
+	send_message(&mut filter, &HintText { text: &format_c(args, false) })
+}
+
+/// Shows a hint box to all connected players.
+pub fn print_hint_all(args: fmt::Arguments<'_>) -> bool {
+	send_message(&mut all_players_filter(), &HintText { text: &format_c(args, false) })
+}
@@ -0,0 +1,65 @@
+use ::rse_cpp::VtObjectMut;
+use ::rse_game_interfaces::EngineTraceImpl;
+use ::rse_math::Vector;
+
+pub use ::rse_game_interfaces::{
+	cppdef::TraceType,
+	TraceFilter, WorldOnly,
+};
+pub use ::rse_shared::cppdef::{
+	entities::HandleEntityVt,
+	Contents, DispSurfFlags, SurfFlags,
+	Ray, Trace,
+};
+
+use super::{
+	ENGINE_TRACE,
+	read_optional, with_edict,
+};
+
+/// Traces `ray` through the world and the entities accepted by `filter`,
+/// stopping at anything with contents in `mask`.
+///
+/// Returns `None` if the engine trace interface isn't available.
+pub fn trace_ray(ray: &Ray, mask: Contents, filter: impl TraceFilter) -> Option<Trace> {
+	read_optional(&ENGINE_TRACE, move |trace| trace.trace_ray(ray, mask, filter))
+}
+
+/// Traces a line from `start` to `end`.
+///
+/// See [`trace_ray`].
+pub fn trace_line(start: Vector, end: Vector, mask: Contents, filter: impl TraceFilter) -> Option<Trace> {
+	trace_ray(&Ray::line(start, end), mask, filter)
+}
+
+/// Sweeps the box from `mins` to `maxs` from `start` to `end`.
+///
+/// See [`trace_ray`].
+pub fn trace_hull(
+	start: Vector, end: Vector, mins: Vector, maxs: Vector,
+	mask: Contents, filter: impl TraceFilter,
+) -> Option<Trace> {
+	trace_ray(&Ray::hull(start, end, mins, maxs), mask, filter)
+}
+
+/// Traces `ray` against `entity` only.
+pub fn clip_ray_to_entity(ray: &Ray, mask: Contents, entity: VtObjectMut<HandleEntityVt>) -> Option<Trace> {
+	read_optional(&ENGINE_TRACE, move |trace| trace.clip_ray_to_entity(ray, mask, entity))
+}
+
+/// Returns the contents of the world and entities at `position`.
+pub fn point_contents(position: &Vector) -> Contents {
+	read_optional(&ENGINE_TRACE, move |trace| trace.point_contents(position)).unwrap_or_default()
+}
+
+/// Returns `true` if nothing in the world blocks the line of sight of players from `start` to `end`.
+pub fn is_visible(start: Vector, end: Vector) -> bool {
+	trace_line(start, end, Contents::MASK_VISIBLE, WorldOnly).is_some_and(move |trace| !trace.did_hit())
+}
+
+/// Returns `true` if `trace` hit the world entity.
+pub fn hit_world(trace: &Trace) -> bool {
+	with_edict(0, move |world| world.base_entity())
+		.flatten()
+		.is_some_and(move |world| trace.hit_world(world.as_ptr()))
+}
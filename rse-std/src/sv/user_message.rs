@@ -0,0 +1,150 @@
+use ::core::{
+	cell::RefCell,
+	ffi::{
+		CStr, c_int,
+	},
+};
+use ::rse_game_interfaces::{
+	PlayerInfo2, PlayerInfoImpl, PlayerInfoManagerImpl, VEngineServerImpl,
+	ServerGameDllImpl, MAX_USER_MESSAGE_NAME_LENGTH,
+};
+use ::rse_math::Vector;
+use rust_alloc::{
+	ffi::CString,
+	vec::Vec,
+};
+
+pub use ::rse_game_interfaces::{
+	cppdef::PlayerBitVec,
+	user_messages::{
+		self, UserMessageData,
+	},
+	RecipientFilter, RecipientFilterImpl,
+};
+pub use ::rse_shared::bitbuf::BitWriter;
+
+use crate::threads::MainThreadBound;
+
+use super::{
+	GAME_DLL, PLAYER_INFO,
+	max_clients, read, read_from, write,
+};
+
+/// Names of all user messages, indexed by their type.
+static USER_MESSAGES: MainThreadBound<RefCell<Option<Vec<CString>>>> =
+	MainThreadBound::new(RefCell::new(None));
+
+pub(super) fn reset_cache() {
+	if let Some(mut cache) = USER_MESSAGES.get().and_then(move |cell| cell.try_borrow_mut().ok()) {
+		*cache = None;
+	}
+}
+
+/// Returns the type of the user message named `name`, compared case-insensitively,
+/// or `None` if there is no such message or the game DLL interface isn't available.
+/// 
+/// User message types differ between mods,
+/// so they should be looked up by name rather than hard-coded.
+/// The names of all user messages are cached on the first call.
+pub fn user_message_id(name: &CStr) -> Option<c_int> {
+	let cache = USER_MESSAGES.get()?;
+	let mut cache = cache.try_borrow_mut().ok()?;
+	let names = match *cache {
+		Some(ref names) => names,
+		None => {
+			let dll = GAME_DLL.get()?.try_borrow().ok()?;
+			let dll = dll.as_ref()?;
+			let mut names = Vec::new();
+			let mut buffer = [0; MAX_USER_MESSAGE_NAME_LENGTH];
+			while let Some(info) = dll.user_message_info(names.len() as _, &mut buffer) {
+				names.push(info.name.into());
+			}
+			cache.insert(names)
+		}
+	};
+	names.iter()
+		.position(move |n| n.to_bytes().eq_ignore_ascii_case(name.to_bytes()))
+		.map(move |msg_type| msg_type as _)
+}
+
+/// Returns the name of the user message of type `msg_type`.
+/// 
+/// See also [`user_message_id`].
+pub fn user_message_name(msg_type: c_int) -> Option<CString> {
+	let dll = GAME_DLL.get()?.try_borrow().ok()?;
+	let mut buffer = [0; MAX_USER_MESSAGE_NAME_LENGTH];
+	Some(dll.as_ref()?.user_message_info(msg_type, &mut buffer)?.name.into())
+}
+
+/// Sends a user message of type `msg_type` to the recipients of `filter`,
+/// with the fields written by `write_fields`.
+///
+/// Returns `false` if the message couldn't be started.
+pub fn send_user_message(
+	filter: &mut RecipientFilter, msg_type: c_int,
+	write_fields: impl FnOnce(&mut BitWriter<'_>),
+) -> bool {
+	write(move |srv| if let Some(srv) = srv {
+		match srv.user_message(filter, msg_type) {
+			Some(mut message) => {
+				write_fields(&mut message);
+				true
+			}
+			None => false,
+		}
+	} else {
+		false
+	})
+}
+
+/// Sends `message` to the recipients of `filter`,
+/// looking up the type of the message with [`user_message_id`].
+///
+/// Returns `false` if the mod doesn't have the message or it couldn't be started.
+pub fn send_message<M: UserMessageData>(filter: &mut RecipientFilter, message: &M) -> bool {
+	let Some(msg_type) = user_message_id(M::NAME) else { return false };
+	send_user_message(filter, msg_type, move |writer| message.write(writer))
+}
+
+/// Returns a filter with all connected players for which `f` returns `true` as recipients.
+fn players_where(f: impl Fn(&PlayerInfo2) -> bool) -> RecipientFilter {
+	let mut filter = RecipientFilter::new();
+	read(|srv| read_from(&PLAYER_INFO, |pim| {
+		let (Some(srv), Some(pim)) = (srv, pim) else { return };
+		for index in 1..=max_clients() {
+			let Some(info) = srv.edict(index).and_then(|edict| pim.player_info(edict)) else { continue };
+			if info.is_connected() && f(info) {
+				filter.add_recipient(index);
+			}
+		}
+	}));
+	filter
+}
+
+/// Returns a reliable filter with all connected players as recipients.
+pub fn all_players_filter() -> RecipientFilter {
+	players_where(move |_| true).reliable()
+}
+
+/// Returns a reliable filter with all connected players on `team` as recipients.
+pub fn team_filter(team: c_int) -> RecipientFilter {
+	players_where(move |info| info.team_index() == team).reliable()
+}
+
+fn multicast_filter(use_pas: bool, origin: &Vector) -> RecipientFilter {
+	let mut filter = RecipientFilter::new();
+	read(|srv| if let Some(srv) = srv {
+		filter.add_recipients_by_bits(&srv.multicast_recipients(use_pas, origin));
+	});
+	filter
+}
+
+/// Returns an unreliable filter with all players that can potentially see `origin` as recipients.
+pub fn pvs_filter(origin: &Vector) -> RecipientFilter {
+	multicast_filter(false, origin)
+}
+
+/// Returns an unreliable filter with all players that can potentially hear `origin` as recipients.
+pub fn pas_filter(origin: &Vector) -> RecipientFilter {
+	multicast_filter(true, origin)
+}
@@ -0,0 +1,72 @@
+use ::core::ffi::c_int;
+use ::rse_game_interfaces::VEngineServerImpl;
+use ::rse_math::Vector;
+
+pub use ::rse_game_interfaces::{
+	Pvs, AreaBits,
+};
+
+use super::{
+	read, write,
+};
+
+/// Returns the visibility cluster that contains `origin`, or `-1` if it's outside of the world.
+pub fn cluster_for_origin(origin: &Vector) -> c_int {
+	read(move |srv| srv.map_or(-1, move |srv| srv.cluster_for_origin(origin)))
+}
+
+/// Returns the PVS of the visibility cluster that contains `origin`.
+pub fn pvs_for_origin(origin: &Vector) -> Pvs {
+	read(move |srv| srv.map(move |srv| srv.pvs_for_origin(origin)).unwrap_or_default())
+}
+
+/// Returns the PVS of the visibility cluster `cluster`.
+pub fn pvs_for_cluster(cluster: c_int) -> Pvs {
+	read(move |srv| srv.map(move |srv| srv.pvs_for_cluster(cluster)).unwrap_or_default())
+}
+
+/// Extension methods of [`Pvs`] that use the engine to look up clusters.
+pub trait PvsExt {
+	/// Returns `true` if `point` is in a cluster of the set.
+	fn contains_point(&self, point: &Vector) -> bool;
+	/// Returns `true` if the box from `mins` to `maxs` touches a cluster of the set.
+	fn contains_box(&self, mins: &Vector, maxs: &Vector) -> bool;
+}
+
+impl PvsExt for Pvs {
+	fn contains_point(&self, point: &Vector) -> bool {
+		read(move |srv| srv.is_some_and(move |srv| srv.is_origin_in_pvs(point, self)))
+	}
+	fn contains_box(&self, mins: &Vector, maxs: &Vector) -> bool {
+		read(move |srv| srv.is_some_and(move |srv| srv.is_box_in_pvs(mins, maxs, self)))
+	}
+}
+
+/// Returns the area that contains `origin`.
+pub fn area_for_origin(origin: &Vector) -> c_int {
+	read(move |srv| srv.map_or(0, move |srv| srv.area(origin)))
+}
+
+/// Returns `true` if `area1` and `area2` are connected through open area portals.
+pub fn areas_connected(area1: c_int, area2: c_int) -> bool {
+	read(move |srv| srv.is_some_and(move |srv| srv.areas_connected(area1, area2)))
+}
+
+/// Returns the areas that can be seen from `area`.
+pub fn area_bits(area: c_int) -> AreaBits {
+	read(move |srv| srv.map(move |srv| srv.area_bits(area)).unwrap_or_default())
+}
+
+/// Opens or closes the area portal numbered `portal`.
+pub fn set_area_portal_open(portal: c_int, is_open: bool) {
+	write(move |srv| if let Some(srv) = srv {
+		srv.set_area_portal_state(portal, is_open)
+	})
+}
+
+/// Opens or closes multiple area portals at once, given their numbers and whether they should be open.
+pub fn set_area_portals_open(states: impl IntoIterator<Item = (c_int, bool)>) {
+	write(move |srv| if let Some(srv) = srv {
+		srv.set_area_portal_states(states)
+	})
+}